@@ -0,0 +1,82 @@
+//! Simple computer players.
+
+use game_state::{Action, GameState};
+
+/// Chooses a move for the current player greedily.
+///
+/// Among the legal actions, picks the piece placement that maximizes the number of positions
+/// covered plus the piece’s button income minus its cost, preferring the earliest such action
+/// on ties. Advances if no piece can be taken, and places any pending patch at its first legal
+/// position. Returns `None` if the game is over.
+pub fn greedy_move(state: &GameState) -> Option<Action> {
+    if state.is_game_over() {
+        return None;
+    }
+
+    let actions = state.legal_actions();
+    if let Some(&action @ Action::PlacePatch { .. }) = actions.first() {
+        return Some(action);
+    }
+
+    let mut best: Option<(i64, Action)> = None;
+
+    for action in actions {
+        if let Action::TakePiece { depth, .. } = action {
+            let piece = state.piece_board().peek(depth).expect("legal actions are takeable");
+            let score = piece.size() as i64 + piece.button_income() as i64 - piece.cost() as i64;
+
+            if best.is_none_or(|(best_score, _)| score > best_score) {
+                best = Some((score, action));
+            }
+        }
+    }
+
+    Some(best.map_or(Action::Advance, |(_, action)| action))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use game_state::GameBuilder;
+    use piece::examples;
+    use position::{Position, Transformation};
+
+    #[test]
+    fn greedy_move_is_legal() {
+        let mut game = GameBuilder::empty()
+            .with_piece_board(|b| b.extend(vec![examples::piece0(),
+                                                examples::piece1(),
+                                                examples::piece2(),
+                                                examples::piece3()]))
+            .build_in_order();
+
+        // piece1 scores 6 + 0 - 1 = 5, the best of the affordable pieces.
+        let action = greedy_move(&game).unwrap();
+        assert_eq!(action, Action::TakePiece {
+            depth:          1,
+            position:       Position::new(0, 0),
+            transformation: Transformation::identity(),
+        });
+        assert_eq!(game.apply(action), Ok(()));
+        assert_eq!(greedy_move(&game), greedy_move(&game.clone()));
+    }
+
+    #[test]
+    fn greedy_move_values_button_income() {
+        use piece::Piece;
+
+        let shape = vec![Position::new(1, 0), Position::new(0, 1), Position::new(1, 1)];
+        let collects = Piece::new(shape.clone(), 1, 1, 5);
+        let pays = Piece::new(shape, 1, 1, 0).with_button_income(2);
+
+        let game = GameBuilder::empty()
+            .with_piece_board(|b| b.extend(vec![collects, pays]))
+            .build_in_order();
+
+        assert_eq!(greedy_move(&game), Some(Action::TakePiece {
+            depth:          1,
+            position:       Position::new(0, 0),
+            transformation: Transformation::identity(),
+        }));
+    }
+}
@@ -1,16 +1,97 @@
 //! The state of a whole quilting game.
 
+use std::cmp;
+use std::collections::BTreeSet;
 use std::default::Default;
 
-use position::Dimension;
-use player::{self, PlayerState, PlayOrder};
+use serde_json;
+
+use piece::Piece;
+use position::{Dimension, Position, Transformation};
+use player::{self, Player, PlayerState, PlayOrder};
 use piece_board::{PieceBoard, PieceBoardBuilder};
 use quilt_board;
+use result::{LoadError, QResult, PlayerError};
 use time_board::{TimeBoard, TimeBoardBuilder};
+use util::saturating_i64;
 
 /// The default size of the square needed to get the bonus.
 pub const DEFAULT_BONUS_SQUARE_SIZE: usize = 7;
 
+/// The number of points awarded to the first player to cover the bonus square.
+pub const BONUS_POINTS: usize = 7;
+
+/// Identifies a one-time bonus given by
+/// [`GameState::award_first_bonus`](struct.GameState.html#method.award_first_bonus).
+///
+/// The numbering is up to the caller; each distinct id is awarded at most once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct BonusId(pub usize);
+
+/// A move that the current player can make on their turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    /// Buy the piece at the given depth in the piece queue, and place it on the quilt board at
+    /// the given position under the given transformation.
+    TakePiece {
+        depth:          usize,
+        position:       Position,
+        transformation: Transformation,
+    },
+    /// Move just past the next player on the time board instead of buying a piece.
+    Advance,
+    /// Place a patch picked up from the time board at the given position of the quilt board.
+    ///
+    /// This is the only action allowed while a patch is pending.
+    PlacePatch {
+        position: Position,
+    },
+}
+
+/// A record of the actions taken in a game, in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameLog {
+    actions: Vec<Action>,
+}
+
+impl GameLog {
+    /// Creates a new, empty log.
+    pub fn new() -> Self {
+        GameLog {
+            actions: Vec::new(),
+        }
+    }
+
+    /// Records an action at the end of the log.
+    pub fn push(&mut self, action: Action) {
+        self.actions.push(action);
+    }
+
+    /// Views the recorded actions in order.
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+}
+
+/// Named rulesets that configure a [`GameBuilder`](struct.GameBuilder.html) all at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// The standard game: a 9-by-9 quilt, a 7-by-7 bonus square, 5 starting currency, and the
+    /// built-in pieces and time board.
+    Classic,
+}
+
+/// Why a game ended, from
+/// [`GameState::game_over_reason`](struct.GameState.html#method.game_over_reason).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameOverReason {
+    /// Every player has reached the end of the time board.
+    AllPlayersFinished,
+    /// One player has reached the end of the time board, in a game built with
+    /// [`all_must_finish(false)`](struct.GameBuilder.html#method.all_must_finish).
+    PlayerFinished,
+}
+
 /// Builder for configuring and constructing new games.
 #[derive(Debug, Clone)]
 pub struct GameBuilder {
@@ -20,14 +101,16 @@ pub struct GameBuilder {
     starting_currency: usize,
     quilt_dimension:   Dimension,
     bonus_square_size: Option<usize>,
+    all_must_finish:   bool,
+    starting_placements: Vec<(Position, Piece, Transformation)>,
 }
 
 impl GameBuilder {
     /// Creates a new builder with the default parameters.
+    ///
+    /// This is the [`Classic`](enum.Preset.html#variant.Classic) preset.
     pub fn new() -> Self {
-        let mut result = Self::empty();
-        result.piece_board = PieceBoardBuilder::default();
-        result
+        Self::empty().preset(Preset::Classic)
     }
 
     /// Creates a new builder whose
@@ -41,9 +124,29 @@ impl GameBuilder {
             starting_currency: player::DEFAULT_STARTING_CURRENCY,
             quilt_dimension:   Dimension::square(quilt_board::DEFAULT_DIMENSION),
             bonus_square_size: Some(DEFAULT_BONUS_SQUARE_SIZE),
+            all_must_finish:   true,
+            starting_placements: Vec::new(),
         }
     }
 
+    /// Configures the quilt size, bonus square size, starting currency, pieces, and time board
+    /// according to the given preset.
+    ///
+    /// The number of players is unchanged.
+    pub fn preset(mut self, preset: Preset) -> Self {
+        match preset {
+            Preset::Classic => {
+                self.piece_board       = PieceBoardBuilder::new();
+                self.time_board        = TimeBoardBuilder::new();
+                self.starting_currency = player::DEFAULT_STARTING_CURRENCY;
+                self.quilt_dimension   = Dimension::square(quilt_board::DEFAULT_DIMENSION);
+                self.bonus_square_size = Some(DEFAULT_BONUS_SQUARE_SIZE);
+            }
+        }
+
+        self
+    }
+
     /// Changes the builder to use the given
     /// [`PieceBoardBuilder`](../piece_board/struct.PieceBoardBuilder.html).
     pub fn piece_board(mut self, piece_board: PieceBoardBuilder) -> Self {
@@ -68,11 +171,8 @@ impl GameBuilder {
 
     /// Changes the builder to use the given number of players.
     ///
-    /// # Errors
-    ///
-    /// Panics if `nplayers < 2`.
+    /// Building fails with `PlayerError::TooFewPlayers` if `nplayers < 2`.
     pub fn nplayers(mut self, nplayers: usize) -> Self {
-        assert!(nplayers >= 2, "Must have at least two players.");
         self.nplayers = nplayers;
         self
     }
@@ -107,11 +207,50 @@ impl GameBuilder {
         self
     }
 
-    fn build_shuffle(self, shuffle: bool) -> GameState {
+    /// Chooses whether the game ends only once every player reaches the end of the time board
+    /// (the default), or as soon as any player does.
+    pub fn all_must_finish(mut self, all_must_finish: bool) -> Self {
+        self.all_must_finish = all_must_finish;
+        self
+    }
+
+    /// Places the given pieces on every player’s quilt at the start of the game.
+    ///
+    /// The pieces are free, but count toward each player’s income. Building fails if the
+    /// placements do not fit together on the quilt.
+    pub fn starting_placements(mut self, placements: Vec<(Position, Piece, Transformation)>)
+                               -> Self
+    {
+        self.starting_placements = placements;
+        self
+    }
+
+    fn build_shuffle(self, shuffle: bool) -> QResult<GameState> {
+        if self.quilt_dimension.width == 0 || self.quilt_dimension.height == 0 {
+            return Err(PlayerError::EmptyQuilt);
+        } else if self.nplayers < 2 {
+            return Err(PlayerError::TooFewPlayers);
+        } else if self.piece_board.is_empty() {
+            return Err(PlayerError::OutOfPieces);
+        }
+
+        if let Some(size) = self.bonus_square_size {
+            let Dimension { width, height } = self.quilt_dimension;
+            if size > cmp::min(width, height) {
+                return Err(PlayerError::BonusSquareTooLarge);
+            }
+        }
+
         let mut players = Vec::new();
 
         for _ in 0 .. self.nplayers {
-            players.push(PlayerState::new(self.quilt_dimension, self.starting_currency))
+            let mut state = PlayerState::new(self.quilt_dimension, self.starting_currency);
+
+            for &(position, ref piece, transformation) in &self.starting_placements {
+                state.place_piece(position, piece.clone(), transformation)?;
+            }
+
+            players.push(state)
         }
 
         let piece_board;
@@ -125,25 +264,55 @@ impl GameBuilder {
             play_order  = PlayOrder::new_in_order(self.nplayers);
         }
 
-        GameState {
-            piece_board:       piece_board,
+        Ok(GameState {
+            piece_board,
             time_board:        self.time_board.build(play_order),
             players:           players.into_boxed_slice(),
             bonus_square_size: self.bonus_square_size,
-        }
+            bonuses_taken:     BTreeSet::new(),
+            turns_taken:       0,
+            all_must_finish:   self.all_must_finish,
+            pending_patches:   Vec::new(),
+        })
     }
 
     /// Builds the game, shuffling the [`PieceBoard`](../piece_board/struct.PieceBoard.html)
     /// and the play order.
-    pub fn build(self) -> GameState {
+    ///
+    /// Returns `Err` if the quilt dimension is empty, there are fewer than two players, there
+    /// are no pieces, the bonus square does not fit on the quilt, or the starting placements
+    /// are illegal.
+    pub fn try_build(self) -> QResult<GameState> {
         self.build_shuffle(true)
     }
 
     /// Builds the game without shuffling the [`PieceBoard`](../piece_board/struct.PieceBoard.html)
     /// and the play order.
-    pub fn build_in_order(self) -> GameState {
+    ///
+    /// Returns `Err` under the same conditions as [`try_build`](#method.try_build).
+    pub fn try_build_in_order(self) -> QResult<GameState> {
         self.build_shuffle(false)
     }
+
+    /// Builds the game, shuffling the [`PieceBoard`](../piece_board/struct.PieceBoard.html)
+    /// and the play order.
+    ///
+    /// # Errors
+    ///
+    /// Panics if [`try_build`](#method.try_build) would return `Err`.
+    pub fn build(self) -> GameState {
+        self.try_build().unwrap_or_else(|e| panic!("GameBuilder::build: {}", e))
+    }
+
+    /// Builds the game without shuffling the [`PieceBoard`](../piece_board/struct.PieceBoard.html)
+    /// and the play order.
+    ///
+    /// # Errors
+    ///
+    /// Panics if [`try_build`](#method.try_build) would return `Err`.
+    pub fn build_in_order(self) -> GameState {
+        self.try_build_in_order().unwrap_or_else(|e| panic!("GameBuilder::build_in_order: {}", e))
+    }
 }
 
 impl Default for GameBuilder {
@@ -155,7 +324,11 @@ impl Default for GameBuilder {
 /// The state of the game.
 ///
 /// Configure and construct with [`GameBuilder`](struct.GameBuilder.html).
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Equality is exact: two states are equal when their piece queues hold the same pieces in the
+/// same order with the same take depth, their time boards match square for square, and every
+/// player’s state matches. This makes it suitable for checking replays.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GameState {
     /// The board from which pieces are selected.
     piece_board:       PieceBoard,
@@ -165,12 +338,50 @@ pub struct GameState {
     players:           Box<[PlayerState]>,
     /// The size quilt square to build to get the bonus, if it remains.
     bonus_square_size: Option<usize>,
+    /// The bonuses given by `award_first_bonus` that have been awarded.
+    #[serde(default)]
+    bonuses_taken:     BTreeSet<BonusId>,
+    /// The number of actions successfully applied.
+    turns_taken:       usize,
+    /// Whether the game continues until every player reaches the end of the time board.
+    all_must_finish:   bool,
+    /// Patches picked up from the time board that must be placed before play continues, with
+    /// the player who must place each.
+    #[serde(default)]
+    pending_patches:   Vec<(Player, Piece)>,
 }
 
 impl GameState {
     /// Is the game over?
+    ///
+    /// By default the game ends once every player has reached the end of the time board. If
+    /// built with [`all_must_finish(false)`](struct.GameBuilder.html#method.all_must_finish),
+    /// it ends as soon as any player has.
     pub fn is_game_over(&self) -> bool {
-        self.time_board.is_game_over()
+        self.game_over_reason().is_some()
+    }
+
+    /// Why has the game ended?
+    ///
+    /// Returns `None` if the game is still going. Running out of pieces does not end the game,
+    /// since players may always advance.
+    pub fn game_over_reason(&self) -> Option<GameOverReason> {
+        if ! self.pending_patches.is_empty() {
+            None
+        } else if self.all_must_finish {
+            if self.time_board.all_finished() {
+                Some(GameOverReason::AllPlayersFinished)
+            } else {
+                None
+            }
+        } else {
+            let last = self.time_board.index_of_last_square();
+            if self.time_board.squares()[last].has_player() {
+                Some(GameOverReason::PlayerFinished)
+            } else {
+                None
+            }
+        }
     }
 
     /// Gets a reference to the piece board.
@@ -178,6 +389,16 @@ impl GameState {
         &self.piece_board
     }
 
+    /// Gets the number of pieces left in the piece queue.
+    pub fn remaining_piece_count(&self) -> usize {
+        self.piece_board.len()
+    }
+
+    /// Gets the total number of positions covered by the pieces left in the piece queue.
+    pub fn remaining_piece_area(&self) -> usize {
+        self.piece_board.pieces().map(Piece::size).sum()
+    }
+
     /// Gets a reference to the time board.
     pub fn time_board(&self) -> &TimeBoard {
         &self.time_board
@@ -185,7 +406,367 @@ impl GameState {
 
     /// Gets the states of the players.
     pub fn player_states(&self) -> &[PlayerState] {
-        &*self.players
+        &self.players
+    }
+
+    /// Gets the state of the given player.
+    ///
+    /// # Errors
+    ///
+    /// Panics if `player` is not in this game.
+    pub fn player_state(&self, player: &Player) -> &PlayerState {
+        &self.players[player.to_usize()]
+    }
+
+    /// Gets the player whose turn it is.
+    ///
+    /// While a patch is pending, this is the player who must place it. Returns `None` if the
+    /// game is over.
+    pub fn current_player(&self) -> Option<&Player> {
+        if let Some((player, _)) = self.pending_patches.first() {
+            Some(player)
+        } else if self.is_game_over() {
+            None
+        } else {
+            self.time_board.current_player()
+        }
+    }
+
+    /// Gets the player whose turn it is, if the game is not over.
+    ///
+    /// This is an owned version of [`current_player`](#method.current_player).
+    pub fn turn_of(&self) -> Option<Player> {
+        self.current_player().cloned()
+    }
+
+    /// Gets the number of actions that have been applied so far.
+    pub fn turns_taken(&self) -> usize {
+        self.turns_taken
+    }
+
+    /// Gets the players with the highest score, in order by player number.
+    ///
+    /// Before the game is over, these are the players currently in the lead.
+    pub fn winners(&self) -> Vec<Player> {
+        let best = self.players.iter().map(PlayerState::score).max();
+
+        self.players.iter()
+            .enumerate()
+            .filter(|&(_, state)| Some(state.score()) == best)
+            .map(|(i, _)| Player::from_usize(i))
+            .collect()
+    }
+
+    /// Gets the single winner of a finished game.
+    ///
+    /// Among the players with the highest score, the winner is the one who reached the last
+    /// square of the time board first. Players who never reached the last square (possible
+    /// when [`all_must_finish(false)`](struct.GameBuilder.html#method.all_must_finish)) rank
+    /// after those who did, and any remaining tie goes to the lowest player number.
+    ///
+    /// Returns `None` if the game is not over, since the finish order is not yet known.
+    pub fn winner_with_tiebreak(&self) -> Option<Player> {
+        if ! self.is_game_over() {
+            return None;
+        }
+
+        let last = self.time_board.index_of_last_square();
+        // Later arrivals are stacked on top, so this lists the finishers last to first.
+        let finishers: Vec<&Player> = self.time_board.squares()[last].players().collect();
+        let finish_rank = |player: &Player| {
+            finishers.iter()
+                .position(|&p| p == player)
+                .map_or(usize::MAX, |i| finishers.len() - 1 - i)
+        };
+
+        self.winners().into_iter().min_by_key(|player| (finish_rank(player), player.to_usize()))
+    }
+
+    /// Gets a read-only view of the game, as for spectators.
+    pub fn view(&self) -> GameView<'_> {
+        GameView {
+            piece_board:    &self.piece_board,
+            time_board:     &self.time_board,
+            players:        &self.players,
+            current_player: self.current_player(),
+            is_game_over:   self.is_game_over(),
+        }
+    }
+
+    /// Summarizes the state of the game for clients that do not need all of it.
+    pub fn summary(&self) -> GameSummary {
+        let players = self.players.iter()
+            .map(|state| PlayerSummary {
+                currency:      state.currency(),
+                score:         state.score(),
+                fill_fraction: state.quilt_board().fill_fraction(),
+            })
+            .collect();
+
+        GameSummary {
+            players,
+            current_player:   self.turn_of(),
+            pieces_remaining: self.remaining_piece_count(),
+            is_game_over:     self.is_game_over(),
+        }
+    }
+
+    /// Gets an optimistic bound on the given player’s final score, for pruning searches.
+    ///
+    /// The bound supposes that the player fills every empty position of their quilt for free,
+    /// acquires the button income of every remaining piece before the next collect square,
+    /// earns one unit of currency for every square left to move, and wins the bonus square if
+    /// it remains. No real game can do all of these, so the bound is admissible but far from
+    /// tight. The arithmetic saturates at `i64::MAX`, which keeps it admissible.
+    ///
+    /// # Errors
+    ///
+    /// Panics if `player` is not in this game.
+    pub fn score_upper_bound(&self, player: Player) -> i64 {
+        let state = self.player_state(&player);
+        let start = self.time_board.index_of(&player).unwrap_or(0);
+        let ahead = &self.time_board.squares()[start + 1 ..];
+
+        let collects = ahead.iter().filter(|square| square.collect()).count();
+        let fixed_income = ahead.iter()
+            .filter_map(|square| square.income())
+            .fold(0, usize::saturating_add);
+        let max_income = self.piece_board.pieces()
+            .map(Piece::button_income)
+            .fold(state.income(), usize::saturating_add);
+        let bonus = if self.bonus_square_size.is_some() { BONUS_POINTS } else { 0 };
+
+        [state.currency(), state.bonus(), bonus, ahead.len(), fixed_income,
+         collects.saturating_mul(max_income)]
+            .iter()
+            .fold(0, |total: i64, &n| total.saturating_add(saturating_i64(n)))
+    }
+
+    /// Estimates the cost for the given player to fill the rest of their quilt.
+    ///
+    /// This is a lower bound: it finds the remaining piece with the lowest cost per cell and
+    /// prices each empty cell at that rate, rounding down. No placement of whole pieces can cost
+    /// less. Returns 0 if the quilt is full or no pieces remain.
+    ///
+    /// # Errors
+    ///
+    /// Panics if `player` is not in this game.
+    pub fn min_cost_to_fill(&self, player: Player) -> usize {
+        let quilt_board = self.player_state(&player).quilt_board();
+        let Dimension { width, height } = quilt_board.dimension();
+        let empty = width * height - quilt_board.positions_covered();
+
+        let best = self.piece_board.pieces().min_by(|a, b| {
+            (a.cost() * b.size()).cmp(&(b.cost() * a.size()))
+        });
+
+        match best {
+            Some(piece) => empty * piece.cost() / piece.size(),
+            None        => 0,
+        }
+    }
+
+    /// Gets the pieces within take depth that the given player can afford, with their depths.
+    ///
+    /// # Errors
+    ///
+    /// Panics if `player` is not in this game.
+    pub fn affordable_pieces(&self, player: Player) -> Vec<(usize, &Piece)> {
+        let currency = self.player_state(&player).currency();
+
+        self.piece_board.takeable()
+            .enumerate()
+            .filter(|&(_, piece)| piece.cost() <= currency)
+            .collect()
+    }
+
+    /// Gets every action that the current player may legally take.
+    ///
+    /// This includes taking each affordable piece within reach at every position and distinct
+    /// transformation where it fits, plus `Advance`. While a patch is pending, it is instead
+    /// every position where the patch fits. Returns an empty vector if the game is over.
+    pub fn legal_actions(&self) -> Vec<Action> {
+        let mut result = Vec::new();
+
+        let player = match self.current_player() {
+            Some(player) => player.clone(),
+            None         => return result,
+        };
+
+        let quilt_board = self.player_state(&player).quilt_board();
+
+        if let Some((_, patch)) = self.pending_patches.first() {
+            for (position, _) in quilt_board.legal_placements(patch) {
+                result.push(Action::PlacePatch { position });
+            }
+
+            return result;
+        }
+
+        for (depth, piece) in self.affordable_pieces(player) {
+            for (position, transformation) in quilt_board.legal_placements(piece) {
+                result.push(Action::TakePiece { depth, position, transformation });
+            }
+        }
+
+        result.push(Action::Advance);
+        result
+    }
+
+    /// Gets the actions that [`apply`](#method.apply) is guaranteed to accept in this state.
+    ///
+    /// Applying any one of the returned actions to this state never returns `Err`, so callers
+    /// such as bots need not handle errors. This is currently the same as
+    /// [`legal_actions`](#method.legal_actions), which already excludes pieces the player
+    /// cannot afford and placements that do not fit; this method makes the guarantee part of
+    /// the contract.
+    pub fn legal_actions_affordable_only(&self) -> Vec<Action> {
+        self.legal_actions()
+    }
+
+    /// Performs the given action as the current player’s turn.
+    ///
+    /// Returns `Err` of a reason, leaving the game unchanged, if the action cannot be performed.
+    pub fn apply(&mut self, action: Action) -> QResult<()> {
+        let player = self.current_player().ok_or(PlayerError::GameOver)?.to_usize();
+
+        match action {
+            Action::PlacePatch { position } => {
+                if self.pending_patches.is_empty() {
+                    return Err(PlayerError::NoPendingPatch);
+                }
+
+                let patch = self.pending_patches[0].1.clone();
+                self.players[player].place_piece(position, patch, Transformation::identity())?;
+                self.pending_patches.remove(0);
+                self.discard_unplaceable_patches();
+            }
+
+            _ if ! self.pending_patches.is_empty() => {
+                return Err(PlayerError::PendingPatchPlacement);
+            }
+
+            Action::TakePiece { depth, position, transformation } => {
+                self.players[player].can_buy_piece(position,
+                                                   self.piece_board.peek(depth)?,
+                                                   transformation)?;
+
+                let piece = self.piece_board.take(depth)?;
+                let distance = piece.distance();
+                self.players[player].buy_piece(position, piece, transformation)?;
+                self.move_current_player(player, distance);
+            }
+
+            Action::Advance => {
+                // Move to one past the next player, earning one unit of currency per square
+                // actually moved (which may be fewer near the end of the board).
+                let distance = self.time_board.index_of_next_player() + 1
+                    - self.time_board.index_of_current_player();
+                let moved = self.move_current_player(player, distance);
+                self.players[player].earn(moved);
+            }
+        }
+
+        self.check_bonus(player);
+        self.turns_taken += 1;
+
+        Ok(())
+    }
+
+    /// Replays the actions in the given log, starting from the given state.
+    ///
+    /// Returns the first `Err` encountered, if any action cannot be performed.
+    pub fn replay(initial: GameState, log: &GameLog) -> QResult<GameState> {
+        let mut result = initial;
+
+        for &action in log.actions() {
+            result.apply(action)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the game state that results from performing the given action, leaving this
+    /// state unchanged.
+    pub fn with_action(&self, action: Action) -> QResult<GameState> {
+        let mut result = self.clone();
+        result.apply(action)?;
+        Ok(result)
+    }
+
+    /// Performs the given action on behalf of the given player.
+    ///
+    /// Like [`apply`](#method.apply), but first checks that it is `player`’s turn, returning
+    /// `Err(PlayerError::NotPlayersTurn)` and leaving the game unchanged if not.
+    pub fn apply_as(&mut self, player: &Player, action: Action) -> QResult<()> {
+        match self.current_player() {
+            None                               => Err(PlayerError::GameOver),
+            Some(current) if current != player => Err(PlayerError::NotPlayersTurn),
+            Some(_)                            => self.apply(action),
+        }
+    }
+
+    /// Awards the given number of bonus points to the given player, if this is the first call
+    /// for the given bonus.
+    ///
+    /// Returns whether the points were awarded, which they are not if `player` is not in the
+    /// game. Each bonus is separate from the others and from the bonus for covering a square of
+    /// the quilt, so several one-time awards can be modeled with distinct ids.
+    pub fn award_first_bonus(&mut self, bonus: BonusId, player: Player, points: usize) -> bool {
+        if self.bonuses_taken.contains(&bonus) {
+            return false;
+        }
+
+        match self.players.get_mut(player.to_usize()) {
+            Some(state) => state.award_bonus(points),
+            None        => return false,
+        }
+
+        self.bonuses_taken.insert(bonus);
+        true
+    }
+
+    /// Moves the current player along the time board, collecting income and any fixed income
+    /// as they go.
+    ///
+    /// Returns the distance actually moved.
+    fn move_current_player(&mut self, player: usize, distance: usize) -> usize {
+        if distance == 0 {
+            return 0;
+        }
+
+        let move_result = self.time_board.move_player(distance);
+
+        for _ in 0 .. move_result.collects {
+            self.players[player].collect();
+        }
+
+        self.players[player].earn(move_result.fixed_income);
+
+        for patch in move_result.pieces {
+            self.pending_patches.push((Player::from_usize(player), patch));
+        }
+        self.discard_unplaceable_patches();
+
+        move_result.distance
+    }
+
+    /// Drops any pending patches that no longer fit anywhere on their player’s quilt board.
+    fn discard_unplaceable_patches(&mut self) {
+        let players = &self.players;
+        self.pending_patches.retain(|(player, patch)| {
+            players[player.to_usize()].quilt_board().can_fit_anywhere(patch)
+        });
+    }
+
+    /// Awards the bonus to the given player if it remains and they have earned it.
+    fn check_bonus(&mut self, player: usize) {
+        if let Some(size) = self.bonus_square_size {
+            if self.players[player].quilt_board().is_square_covered(size) {
+                self.players[player].award_bonus(BONUS_POINTS);
+                self.bonus_square_size = None;
+            }
+        }
     }
 }
 
@@ -193,4 +774,811 @@ impl Default for GameState {
     fn default() -> Self {
         GameBuilder::new().build()
     }
-}
\ No newline at end of file
+}
+
+/// A read-only view of a [`GameState`](struct.GameState.html), from
+/// [`GameState::view`](struct.GameState.html#method.view).
+#[derive(Debug, Clone, Copy)]
+pub struct GameView<'a> {
+    piece_board:    &'a PieceBoard,
+    time_board:     &'a TimeBoard,
+    players:        &'a [PlayerState],
+    current_player: Option<&'a Player>,
+    is_game_over:   bool,
+}
+
+impl<'a> GameView<'a> {
+    /// Gets the piece board.
+    pub fn piece_board(&self) -> &'a PieceBoard {
+        self.piece_board
+    }
+
+    /// Gets the time board.
+    pub fn time_board(&self) -> &'a TimeBoard {
+        self.time_board
+    }
+
+    /// Gets the states of all the players, indexed by player number.
+    pub fn player_states(&self) -> &'a [PlayerState] {
+        self.players
+    }
+
+    /// Gets the state of the given player.
+    ///
+    /// # Errors
+    ///
+    /// Panics if `player` is not in this game.
+    pub fn player_state(&self, player: &Player) -> &'a PlayerState {
+        &self.players[player.to_usize()]
+    }
+
+    /// Gets the player whose turn it is, or `None` if the game is over.
+    pub fn current_player(&self) -> Option<&'a Player> {
+        self.current_player
+    }
+
+    /// Gets the state of the player whose turn it is, or `None` if the game is over.
+    pub fn current_player_state(&self) -> Option<&'a PlayerState> {
+        self.current_player.map(|player| self.player_state(player))
+    }
+
+    /// Is the game over?
+    pub fn is_game_over(&self) -> bool {
+        self.is_game_over
+    }
+}
+
+/// A summary of one player’s state, as part of a [`GameSummary`](struct.GameSummary.html).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerSummary {
+    /// The player’s currency.
+    pub currency:      usize,
+    /// The player’s score if the game ended now.
+    pub score:         i64,
+    /// The fraction of the player’s quilt board that is covered.
+    pub fill_fraction: f64,
+}
+
+/// A summary of a [`GameState`](struct.GameState.html), from
+/// [`GameState::summary`](struct.GameState.html#method.summary).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameSummary {
+    /// A summary of each player, indexed by player number.
+    pub players:          Vec<PlayerSummary>,
+    /// The player whose turn it is, if the game is not over.
+    pub current_player:   Option<Player>,
+    /// The number of pieces left in the piece queue.
+    pub pieces_remaining: usize,
+    /// Whether the game is over.
+    pub is_game_over:     bool,
+}
+
+/// The saved game format version written by [`SavedGame`](struct.SavedGame.html).
+pub const SAVE_VERSION: u32 = 1;
+
+/// A `GameState` tagged with the version of its saved format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedGame {
+    version: u32,
+    state:   GameState,
+}
+
+impl SavedGame {
+    /// Wraps the given state for saving in the current format.
+    pub fn new(state: GameState) -> Self {
+        SavedGame {
+            version: SAVE_VERSION,
+            state,
+        }
+    }
+
+    /// Gets the format version.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Gets the saved game state.
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    /// Unwraps the saved game state.
+    pub fn into_state(self) -> GameState {
+        self.state
+    }
+
+    /// Serializes the saved game to JSON.
+    pub fn save(&self) -> String {
+        serde_json::to_string(self).expect("SavedGame serializes to JSON")
+    }
+
+    /// Deserializes a saved game from JSON.
+    ///
+    /// The version is checked before the rest of the payload, so a game saved in an
+    /// unknown format fails with `LoadError::UnsupportedVersion` rather than a parse error.
+    pub fn load(json: &str) -> Result<Self, LoadError> {
+        #[derive(Deserialize)]
+        struct Versioned {
+            version: u32,
+        }
+
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let Versioned { version } = serde_json::from_value(value.clone())?;
+
+        if version != SAVE_VERSION {
+            return Err(LoadError::UnsupportedVersion(version));
+        }
+
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use piece::examples;
+    use bot;
+
+    fn pos(x: usize, y: usize) -> Position {
+        Position::new(x, y)
+    }
+
+    fn game() -> GameState {
+        GameBuilder::empty()
+            .with_piece_board(|b| b.extend(vec![examples::piece0(),
+                                                examples::piece1(),
+                                                examples::piece2(),
+                                                examples::piece3()]))
+            .build_in_order()
+    }
+
+    fn take(depth: usize, x: usize, y: usize) -> Action {
+        Action::TakePiece {
+            depth,
+            position:       pos(x, y),
+            transformation: Transformation::identity(),
+        }
+    }
+
+    #[test]
+    fn try_build() {
+        assert_eq!(GameBuilder::new().quilt_dimension(Dimension::new(0, 9)).try_build(),
+                   Err(PlayerError::EmptyQuilt));
+        assert_eq!(GameBuilder::empty().try_build(), Err(PlayerError::OutOfPieces));
+        assert_eq!(GameBuilder::new().nplayers(1).try_build(), Err(PlayerError::TooFewPlayers));
+
+        let game = GameBuilder::new().try_build().unwrap();
+        assert_eq!(game.player_states().len(), player::DEFAULT_NPLAYERS);
+        assert!(! game.piece_board().is_empty());
+    }
+
+    #[test]
+    fn classic_preset() {
+        assert_eq!(GameBuilder::empty().preset(Preset::Classic).build_in_order(),
+                   GameBuilder::new().build_in_order());
+
+        let game = GameBuilder::empty()
+            .quilt_size(5)
+            .no_bonus()
+            .nplayers(3)
+            .preset(Preset::Classic)
+            .build_in_order();
+        assert_eq!(game.player_states().len(), 3);
+        assert_eq!(game.player_states()[0].quilt_board().dimension(), Dimension::square(9));
+        assert_eq!(game.piece_board().len(), PieceBoardBuilder::new().build().len());
+    }
+
+    #[test]
+    fn bonus_square_must_fit() {
+        assert_eq!(GameBuilder::new()
+                       .quilt_dimension(Dimension::new(5, 9))
+                       .bonus_square_size(7)
+                       .try_build(),
+                   Err(PlayerError::BonusSquareTooLarge));
+
+        assert!(GameBuilder::new()
+                    .quilt_dimension(Dimension::new(9, 7))
+                    .bonus_square_size(7)
+                    .try_build()
+                    .is_ok());
+
+        assert!(GameBuilder::new()
+                    .quilt_dimension(Dimension::new(5, 9))
+                    .no_bonus()
+                    .try_build()
+                    .is_ok());
+    }
+
+    #[test]
+    fn starting_placements() {
+        let placements = vec![(pos(0, 0), examples::piece0(), Transformation::identity())];
+        let game = GameBuilder::new()
+            .starting_placements(placements)
+            .build();
+
+        for state in game.player_states() {
+            assert_eq!(state.quilt_board().positions_covered(), 4);
+            assert_eq!(state.currency(), player::DEFAULT_STARTING_CURRENCY);
+        }
+
+        let overhanging = vec![(pos(8, 0), examples::piece0(), Transformation::identity())];
+        assert_eq!(GameBuilder::new().starting_placements(overhanging).try_build(),
+                   Err(PlayerError::PlacementOverhangsRight));
+    }
+
+    #[test]
+    #[should_panic(expected = "Quilt board has no positions")]
+    fn build_panics_on_error() {
+        GameBuilder::new().quilt_size(0).build();
+    }
+
+    #[test]
+    fn remaining_pieces() {
+        let default = GameBuilder::new().build();
+        let area: usize = default.piece_board().pieces().map(|piece| piece.size()).sum();
+
+        assert_eq!(default.remaining_piece_count(), default.piece_board().len());
+        assert_eq!(default.remaining_piece_area(), area);
+
+        let mut game = game();
+        assert_eq!(game.remaining_piece_count(), 4);
+        assert_eq!(game.remaining_piece_area(), 19);
+        assert_eq!(game.apply(take(0, 0, 0)), Ok(()));
+        assert_eq!(game.remaining_piece_count(), 3);
+        assert_eq!(game.remaining_piece_area(), 15);
+    }
+
+    #[test]
+    fn summary() {
+        let default = GameState::default();
+        let summary = default.summary();
+
+        assert_eq!(summary.players.len(), 2);
+        assert_eq!(summary.current_player, default.turn_of());
+        assert_eq!(summary.pieces_remaining, default.piece_board().len());
+        assert!(! summary.is_game_over);
+
+        let mut game = game();
+        assert_eq!(game.apply(take(0, 0, 0)), Ok(()));
+        let summary = game.summary();
+        assert_eq!(summary.current_player, Some(Player::from_usize(1)));
+        assert_eq!(summary.players[0].currency, player::DEFAULT_STARTING_CURRENCY - 2);
+        assert_eq!(summary.players[0].fill_fraction, 4.0 / 81.0);
+        assert_eq!(summary.players[1].score, player::DEFAULT_STARTING_CURRENCY as i64 - 162);
+    }
+
+    #[test]
+    fn view_reflects_state() {
+        let mut game = game();
+        let first = game.current_player().unwrap().clone();
+        assert_eq!(game.apply(take(0, 0, 0)), Ok(()));
+
+        let view = game.view();
+        assert_eq!(view.piece_board().len(), 3);
+        assert_eq!(view.time_board(), game.time_board());
+        assert_eq!(view.player_states().len(), 2);
+        assert_eq!(view.player_state(&first).quilt_board().positions_covered(), 4);
+        assert_eq!(view.current_player(), game.current_player());
+        assert_eq!(view.current_player_state().unwrap().currency(),
+                   player::DEFAULT_STARTING_CURRENCY);
+        assert!(! view.is_game_over());
+    }
+
+    #[test]
+    fn score_upper_bound_is_admissible() {
+        let mut game = GameBuilder::empty()
+            .with_piece_board(|b| b.extend(vec![examples::piece0(), examples::piece2(),
+                                                examples::piece4()]))
+            .time_board(TimeBoardBuilder::from_slice(br#"[{}, {}, {"collect": true}, {},
+                                                          {"income": 2}, {}]"#).unwrap())
+            .quilt_size(5)
+            .no_bonus()
+            .build_in_order();
+
+        let players = [Player::from_usize(0), Player::from_usize(1)];
+        let bounds: Vec<i64> = players.iter()
+            .map(|player| game.score_upper_bound(player.clone()))
+            .collect();
+
+        // 5 currency, plus 5 squares to move, 2 fixed income, and one collect of 4.
+        assert_eq!(bounds, vec![16, 16]);
+
+        assert_eq!(game.apply(take(2, 0, 0)), Ok(()));
+        while let Some(action) = bot::greedy_move(&game) {
+            assert_eq!(game.apply(action), Ok(()));
+        }
+
+        for (player, &bound) in players.iter().zip(&bounds) {
+            let score = game.player_state(player).score();
+            assert!(score <= bound, "{} > {}", score, bound);
+            assert!(game.score_upper_bound(player.clone()) >= score);
+        }
+    }
+
+    #[test]
+    fn score_upper_bound_saturates() {
+        let huge = Piece::new(vec![pos(0, 0)], 0, 1, 0).with_button_income(usize::MAX);
+        let game = GameBuilder::empty()
+            .with_piece_board(|b| b.extend(vec![huge.clone(), huge]))
+            .time_board(TimeBoardBuilder::from_slice(br#"[{}, {"collect": true}, {"collect": true},
+                                                          {"income": 2}]"#).unwrap())
+            .starting_currency(player::MAX_CURRENCY - 1)
+            .build_in_order();
+
+        let player = game.current_player().unwrap().clone();
+        let bound = game.score_upper_bound(player.clone());
+
+        assert_eq!(bound, i64::MAX);
+        assert!(bound >= game.player_state(&player).score());
+    }
+
+    #[test]
+    fn take_piece() {
+        let mut game = game();
+        let player = game.current_player().unwrap().clone();
+
+        assert_eq!(game.apply(take(0, 0, 0)), Ok(()));
+
+        let state = game.player_state(&player);
+        assert_eq!(state.currency(), player::DEFAULT_STARTING_CURRENCY - 2);
+        assert_eq!(state.quilt_board().positions_covered(), 4);
+        assert_eq!(game.piece_board().len(), 3);
+        assert_eq!(game.time_board().squares()[1].players().next(), Some(&player));
+        assert_ne!(game.current_player(), Some(&player));
+    }
+
+    #[test]
+    fn placed_pieces() {
+        let mut game = game();
+        let first = game.current_player().unwrap().clone();
+
+        assert_eq!(game.apply(take(0, 0, 0)), Ok(()));
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert_eq!(game.apply(take(2, 2, 0)), Ok(()));
+
+        let state = game.player_state(&first);
+        assert_eq!(state.placed_pieces(), &[examples::piece0(), examples::piece3()]);
+        assert_eq!(state.quilt_board().positions_covered(), 7);
+    }
+
+    #[test]
+    fn placed_piece_keeps_id() {
+        let mut game = game();
+        let first = game.current_player().unwrap().clone();
+        let id = game.piece_board().pieces().next().unwrap().id();
+
+        assert!(id.is_some());
+        assert_eq!(game.apply(take(0, 0, 0)), Ok(()));
+        assert_eq!(game.player_state(&first).placed_pieces()[0].id(), id);
+    }
+
+    #[test]
+    fn advance() {
+        let mut game = game();
+        let first = game.current_player().unwrap().clone();
+
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert_eq!(game.time_board().squares()[1].players().next(), Some(&first));
+
+        let second = game.current_player().unwrap().clone();
+        assert_ne!(first, second);
+
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert_eq!(game.time_board().squares()[2].players().next(), Some(&second));
+        assert_eq!(game.current_player(), Some(&first));
+        assert_eq!(game.piece_board().len(), 4);
+    }
+
+    #[test]
+    fn advance_past_trailing_opponent_earns_currency() {
+        let mut game = game();
+        let first = game.current_player().unwrap().clone();
+
+        // The first player jumps ahead to square 2.
+        assert_eq!(game.apply(take(1, 0, 0)), Ok(()));
+        let second = game.current_player().unwrap().clone();
+        assert_eq!(game.time_board().index_of_next_player(), 2);
+
+        // The second player advances from square 0 to square 3.
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert_eq!(game.player_state(&second).currency(),
+                   player::DEFAULT_STARTING_CURRENCY + 3);
+        assert_eq!(game.time_board().squares()[3].players().next(), Some(&second));
+        assert_eq!(game.current_player(), Some(&first));
+    }
+
+    #[test]
+    fn game_over_when_all_finished() {
+        let builder = GameBuilder::empty()
+            .with_piece_board(|b| b.extend(vec![examples::piece0()]))
+            .time_board(TimeBoardBuilder::new().length(4))
+            .nplayers(3);
+
+        let mut game = builder.clone().build_in_order();
+        let mut finished = 0;
+
+        while ! game.is_game_over() {
+            assert_eq!(game.apply(Action::Advance), Ok(()));
+
+            let now_finished = (0 .. 3)
+                .filter(|&i| game.time_board().has_finished(Player::from_usize(i)))
+                .count();
+            assert!(now_finished == 3 || ! game.is_game_over());
+            finished = now_finished;
+        }
+
+        assert_eq!(finished, 3);
+        assert!(game.time_board().all_finished());
+
+        let mut game = builder.all_must_finish(false).build_in_order();
+
+        while ! game.is_game_over() {
+            assert_eq!(game.apply(Action::Advance), Ok(()));
+        }
+
+        assert!(! game.time_board().all_finished());
+        assert_eq!(game.current_player(), None);
+        assert_eq!(game.apply(Action::Advance), Err(PlayerError::GameOver));
+    }
+
+    #[test]
+    fn game_over_reasons() {
+        let builder = GameBuilder::empty()
+            .with_piece_board(|b| b.extend(vec![examples::piece0()]))
+            .time_board(TimeBoardBuilder::from_slice(b"[{}, {}, {}]").unwrap());
+
+        // [01][][] → [1][0][] → [][0][1] → [][][10]
+        let mut game = builder.clone().build_in_order();
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert_eq!(game.game_over_reason(), None);
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert_eq!(game.game_over_reason(), Some(GameOverReason::AllPlayersFinished));
+
+        let mut game = builder.all_must_finish(false).build_in_order();
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert_eq!(game.game_over_reason(), None);
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert_eq!(game.game_over_reason(), Some(GameOverReason::PlayerFinished));
+    }
+
+    #[test]
+    fn running_out_of_pieces_does_not_end_game() {
+        let mut game = GameBuilder::empty()
+            .with_piece_board(|b| b.extend(vec![examples::piece0()]))
+            .build_in_order();
+
+        assert_eq!(game.apply(take(0, 0, 0)), Ok(()));
+        assert_eq!(game.remaining_piece_count(), 0);
+        assert_eq!(game.game_over_reason(), None);
+        assert_eq!(game.legal_actions(), vec![Action::Advance]);
+    }
+
+    #[test]
+    fn winner_tiebreak_by_finish_order() {
+        let mut game = GameBuilder::empty()
+            .with_piece_board(|b| b.extend(vec![examples::piece0()]))
+            .time_board(TimeBoardBuilder::from_slice(b"[{}, {}, {}]").unwrap())
+            .build_in_order();
+
+        // [01][][] → [1][0][] → [][0][1] → [][][10]
+        assert_eq!(game.winner_with_tiebreak(), None);
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+
+        let first = Player::from_usize(0);
+        let second = Player::from_usize(1);
+        assert_eq!(game.player_state(&first).score(), game.player_state(&second).score());
+        assert_eq!(game.winners(), vec![first, second.clone()]);
+        assert_eq!(game.winner_with_tiebreak(), Some(second));
+    }
+
+    #[test]
+    fn patch_must_be_placed() {
+        let mut game = GameBuilder::empty()
+            .with_piece_board(|b| b.extend(vec![examples::piece0()]))
+            .time_board(TimeBoardBuilder::new().length(5).add_patch(1))
+            .build_in_order();
+        let first = game.current_player().unwrap().clone();
+
+        assert_eq!(game.apply(Action::PlacePatch { position: pos(0, 0) }),
+                   Err(PlayerError::NoPendingPatch));
+
+        // The first player passes the patch on square 1.
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert_eq!(game.current_player(), Some(&first));
+        assert_eq!(game.apply(Action::Advance), Err(PlayerError::PendingPatchPlacement));
+        assert_eq!(game.apply(take(0, 0, 0)), Err(PlayerError::PendingPatchPlacement));
+        assert_eq!(game.legal_actions().len(), 81);
+        assert!(game.legal_actions().iter().all(|action| {
+            matches!(*action, Action::PlacePatch { .. })
+        }));
+
+        assert_eq!(game.apply(Action::PlacePatch { position: pos(4, 4) }), Ok(()));
+        assert!(game.player_state(&first).quilt_board().is_position_covered(pos(4, 4)));
+        assert_ne!(game.current_player(), Some(&first));
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+    }
+
+    #[test]
+    fn advance_is_clamped_at_end() {
+        let mut game = GameBuilder::empty()
+            .with_piece_board(|b| b.extend(vec![examples::piece0()]))
+            .time_board(TimeBoardBuilder::from_slice(b"[{}, {}, {}]").unwrap())
+            .build_in_order();
+
+        // [01][][] → [1][0][] → [][0][1] → [][][10]
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert!(game.is_game_over());
+
+        for state in game.player_states() {
+            assert_eq!(state.currency(), player::DEFAULT_STARTING_CURRENCY + 2);
+        }
+    }
+
+    #[test]
+    fn legal_actions_early_game() {
+        let game = game();
+        let actions = game.legal_actions();
+
+        assert_eq!(actions.last(), Some(&Action::Advance));
+        assert!(actions.contains(&take(0, 0, 0)));
+        assert!(actions.contains(&take(1, 0, 0)));
+
+        // piece2 costs more than the starting currency.
+        assert!(! actions.iter().any(|action| match *action {
+            Action::TakePiece { depth, .. } => depth == 2,
+            _                               => false,
+        }));
+    }
+
+    #[test]
+    fn affordable_only_actions_apply() {
+        let mut game = GameBuilder::new()
+            .time_board(TimeBoardBuilder::new().length(8).add_patch(2))
+            .build();
+
+        while ! game.is_game_over() {
+            let actions = game.legal_actions_affordable_only();
+            assert!(! actions.is_empty());
+
+            for &action in &actions {
+                assert!(game.with_action(action).is_ok(), "{:?} failed", action);
+            }
+
+            assert_eq!(game.apply(actions[0]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn with_action_leaves_original() {
+        let game = game();
+        let before = game.clone();
+
+        let after = game.with_action(take(0, 0, 0)).unwrap();
+        assert_eq!(game, before);
+        assert_eq!(after.piece_board().len(), 3);
+
+        let mut applied = game.clone();
+        assert_eq!(applied.apply(take(0, 0, 0)), Ok(()));
+        assert_eq!(after, applied);
+
+        assert_eq!(game.with_action(take(2, 0, 0)), Err(PlayerError::InsufficientFunds));
+    }
+
+    #[test]
+    fn replay_log() {
+        use serde_json;
+
+        let initial = game();
+        let mut game = initial.clone();
+        let mut log = GameLog::new();
+
+        for &action in &[take(0, 0, 0), Action::Advance, take(2, 2, 0), Action::Advance] {
+            assert_eq!(game.apply(action), Ok(()));
+            log.push(action);
+        }
+
+        let json = serde_json::to_vec(&log).unwrap();
+        let mut log: GameLog = serde_json::from_slice(&json).unwrap();
+        assert_eq!(log.actions().len(), 4);
+        assert_eq!(GameState::replay(initial.clone(), &log), Ok(game));
+
+        log.push(take(0, 0, 0));
+        assert_eq!(GameState::replay(initial, &log), Err(PlayerError::PlacementOverlapsPiece));
+    }
+
+    #[test]
+    fn equality_is_structural() {
+        assert_eq!(game(), game());
+
+        let shallow = GameBuilder::empty()
+            .with_piece_board(|b| b.extend(vec![examples::piece0(),
+                                                examples::piece1(),
+                                                examples::piece2(),
+                                                examples::piece3()])
+                                   .depth(1))
+            .build_in_order();
+        assert_ne!(game(), shallow);
+
+        // Identical histories compare equal, and diverging ones do not.
+        let mut a = game();
+        let mut b = game();
+        assert_eq!(a.apply(take(1, 0, 0)), Ok(()));
+        assert_eq!(b.apply(take(1, 0, 0)), Ok(()));
+        assert_eq!(a, b);
+        assert_eq!(a.apply(Action::Advance), Ok(()));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn apply_as_wrong_player() {
+        let mut game = game();
+        let current = game.current_player().unwrap().clone();
+        let waiting = game.time_board().squares()[0].players().nth(1).unwrap().clone();
+        let before = game.clone();
+
+        assert_eq!(game.apply_as(&waiting, take(0, 0, 0)), Err(PlayerError::NotPlayersTurn));
+        assert_eq!(game.apply_as(&waiting, Action::Advance), Err(PlayerError::NotPlayersTurn));
+        assert_eq!(game, before);
+
+        assert_eq!(game.apply_as(&current, Action::Advance), Ok(()));
+        assert_eq!(game.current_player(), Some(&waiting));
+    }
+
+    #[test]
+    fn award_first_bonus_once() {
+        let mut game = game();
+        let first = game.current_player().unwrap().clone();
+        let second = game.time_board().squares()[0].players().nth(1).unwrap().clone();
+
+        assert!(game.award_first_bonus(BonusId(0), first.clone(), 5));
+        assert!(! game.award_first_bonus(BonusId(0), second.clone(), 5));
+        assert!(! game.award_first_bonus(BonusId(0), first.clone(), 5));
+
+        assert_eq!(game.player_state(&first).bonus(), 5);
+        assert_eq!(game.player_state(&second).bonus(), 0);
+    }
+
+    #[test]
+    fn award_distinct_first_bonuses() {
+        let mut game = game();
+        let first = game.current_player().unwrap().clone();
+        let second = game.time_board().squares()[0].players().nth(1).unwrap().clone();
+
+        assert!(game.award_first_bonus(BonusId(0), first.clone(), 5));
+        assert!(game.award_first_bonus(BonusId(1), second.clone(), 3));
+        assert!(! game.award_first_bonus(BonusId(1), first.clone(), 3));
+
+        assert_eq!(game.player_state(&first).bonus(), 5);
+        assert_eq!(game.player_state(&second).bonus(), 3);
+    }
+
+    #[test]
+    fn award_first_bonus_to_unknown_player() {
+        let mut game = game();
+        let first = game.current_player().unwrap().clone();
+
+        assert!(! game.award_first_bonus(BonusId(0), Player::from_usize(7), 5));
+        assert!(game.award_first_bonus(BonusId(0), first, 5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn player_state_panics_on_unknown_player() {
+        game().player_state(&Player::from_usize(7));
+    }
+
+    #[test]
+    #[should_panic]
+    fn view_player_state_panics_on_unknown_player() {
+        game().view().player_state(&Player::from_usize(7));
+    }
+
+    #[test]
+    fn min_cost_to_fill() {
+        let mut game = game();
+        let first = game.current_player().unwrap().clone();
+
+        // piece1 is cheapest per cell: 6 cells for 1. 81 cells cost 13.5, rounded down.
+        assert_eq!(game.min_cost_to_fill(first.clone()), 13);
+
+        assert_eq!(game.apply(take(0, 0, 0)), Ok(()));
+        assert_eq!(game.min_cost_to_fill(first.clone()), 12);
+
+        let mut used_up = GameBuilder::empty()
+            .with_piece_board(|b| b.extend(vec![Piece::single_position()]))
+            .build_in_order();
+        assert_eq!(used_up.apply(take(0, 0, 0)), Ok(()));
+        assert_eq!(used_up.min_cost_to_fill(first), 0);
+    }
+
+    #[test]
+    fn affordable_pieces() {
+        let mut game = game();
+        let first = game.current_player().unwrap().clone();
+        assert_eq!(game.apply(take(0, 0, 0)), Ok(()));
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert_eq!(game.current_player(), Some(&first));
+        assert_eq!(game.player_state(&first).currency(), 3);
+
+        assert_eq!(game.affordable_pieces(first),
+                   vec![(0, &examples::piece1()), (2, &examples::piece3())]);
+    }
+
+    #[test]
+    fn count_turns() {
+        let mut game = game();
+        assert_eq!(game.turns_taken(), 0);
+        assert_eq!(game.turn_of().as_ref(), game.current_player());
+
+        assert_eq!(game.apply(take(0, 0, 0)), Ok(()));
+        assert_eq!(game.turns_taken(), 1);
+
+        assert_eq!(game.apply(take(1, 0, 0)), Err(PlayerError::InsufficientFunds));
+        assert_eq!(game.turns_taken(), 1);
+
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert_eq!(game.turns_taken(), 2);
+        assert_eq!(game.turn_of().as_ref(), game.current_player());
+    }
+
+    #[test]
+    fn advance_earns_fixed_income() {
+        let mut game = GameBuilder::empty()
+            .with_piece_board(|b| b.extend(vec![examples::piece0()]))
+            .time_board(TimeBoardBuilder::from_slice(br#"[{}, {"income": 4}, {}, {}]"#).unwrap())
+            .build_in_order();
+        let first = game.current_player().unwrap().clone();
+
+        assert_eq!(game.apply(Action::Advance), Ok(()));
+        assert_eq!(game.player_state(&first).currency(),
+                   player::DEFAULT_STARTING_CURRENCY + 1 + 4);
+    }
+
+    #[test]
+    fn take_unaffordable_piece() {
+        let mut game = game();
+        let before = game.clone();
+
+        assert_eq!(game.apply(take(2, 0, 0)), Err(PlayerError::InsufficientFunds));
+        assert_eq!(game, before);
+    }
+
+    #[test]
+    fn take_piece_off_board() {
+        let mut game = game();
+        let before = game.clone();
+
+        assert_eq!(game.apply(take(0, 8, 0)), Err(PlayerError::PlacementOverhangsRight));
+        assert_eq!(game.apply(take(3, 0, 0)), Err(PlayerError::TakeOverDepth));
+        assert_eq!(game, before);
+    }
+
+    #[test]
+    fn saved_game_round_trip() {
+        let mut game = game();
+        assert_eq!(game.apply(take(0, 0, 0)), Ok(()));
+
+        let json = SavedGame::new(game.clone()).save();
+        let loaded = SavedGame::load(&json).unwrap();
+        assert_eq!(loaded.version(), SAVE_VERSION);
+        assert_eq!(loaded.into_state(), game);
+    }
+
+    #[test]
+    fn saved_game_rejects_unknown_version() {
+        let mut value = serde_json::to_value(SavedGame::new(game())).unwrap();
+        value["version"] = serde_json::Value::from(SAVE_VERSION + 1);
+
+        match SavedGame::load(&value.to_string()) {
+            Err(LoadError::UnsupportedVersion(version)) => assert_eq!(version, SAVE_VERSION + 1),
+            other => panic!("expected unsupported version, got {:?}", other),
+        }
+
+        assert!(matches!(SavedGame::load("{}"), Err(LoadError::Json(_))));
+    }
+}
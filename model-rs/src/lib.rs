@@ -18,3 +18,5 @@ pub mod player;
 pub mod time_board;
 pub mod piece_board;
 pub mod game_state;
+pub mod bot;
+pub mod util;
@@ -3,8 +3,25 @@
 use std::{cmp, fmt, slice};
 
 use serde::de::{self, Deserialize, Deserializer, Visitor, SeqAccess, MapAccess};
+use serde_json;
 
 use position::{Position, Dimension, Transformation};
+use result::{QResult, PlayerError};
+
+/// An opaque identifier that follows a piece from the piece queue onto a quilt.
+///
+/// Identifiers are assigned in order as pieces are added to a
+/// [`PieceBoardBuilder`](../piece_board/struct.PieceBoardBuilder.html), and are distinct within
+/// one builder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct PieceId(usize);
+
+impl PieceId {
+    /// Creates the identifier with the given index.
+    pub(crate) fn new(index: usize) -> Self {
+        PieceId(index)
+    }
+}
 
 /// A game piece
 ///
@@ -13,36 +30,106 @@ use position::{Position, Dimension, Transformation};
 ///  - The positions fit tightly within the dimension.
 ///
 ///  - The positions are sorted.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+///
+/// Pieces compare equal regardless of their [`id`](#method.id)s.
+#[derive(Debug, Clone, Serialize)]
 pub struct Piece {
     /// The dimensions of the piece.
     #[serde(skip_serializing)]
-    dimension: Dimension,
+    dimension:     Dimension,
     /// The pieces positions.
-    positions: Box<[Position]>,
+    positions:     Box<[Position]>,
     /// The cost of the piece.
-    cost:      usize,
+    cost:          usize,
     /// The distance to move when taking the piece.
-    distance:  usize,
+    distance:      usize,
     /// The value to collect when collecting, if holding the piece.
-    collect:   usize,
+    collect:       usize,
+    /// The income paid to the holder of the piece at each collect square.
+    button_income: usize,
+    /// The identifier of the piece, if it has been through a piece board builder.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id:            Option<PieceId>,
 }
 
 impl Piece {
     /// Constructs a new piece from the given positions, cost, and move distance.
-    pub fn new(mut positions: Vec<Position>, cost: usize, distance: usize, collect: usize) -> Self {
+    ///
+    /// # Errors
+    ///
+    /// Panics if `positions` is empty or not connected.
+    pub fn new(positions: Vec<Position>, cost: usize, distance: usize, collect: usize) -> Self {
+        match Self::try_new(positions, cost, distance, collect) {
+            Ok(piece) => piece,
+            Err(e)    => panic!("Piece::new: {}", e),
+        }
+    }
+
+    /// Constructs a new piece like [`new`](#method.new), also reporting whether the positions
+    /// had to be sorted or deduplicated.
+    ///
+    /// The flag is `false` exactly when `positions` was already strictly increasing.
+    ///
+    /// # Errors
+    ///
+    /// Panics if `positions` is empty or not connected.
+    pub fn new_checked(positions: Vec<Position>, cost: usize, distance: usize, collect: usize)
+                       -> (Self, bool)
+    {
+        let changed = positions.windows(2).any(|pair| pair[0] >= pair[1]);
+        (Self::new(positions, cost, distance, collect), changed)
+    }
+
+    /// Constructs a new piece from the given positions, cost, and move distance.
+    ///
+    /// Returns `Err(PlayerError::EmptyPiece)` if `positions` is empty, or
+    /// `Err(PlayerError::DisconnectedPiece)` if `positions` are not orthogonally connected.
+    pub fn try_new(mut positions: Vec<Position>, cost: usize, distance: usize, collect: usize)
+                   -> QResult<Self>
+    {
+        if positions.is_empty() {
+            return Err(PlayerError::EmptyPiece);
+        }
+
         positions.sort();
         positions.dedup();
 
+        if ! is_connected(&positions) {
+            return Err(PlayerError::DisconnectedPiece);
+        }
+
         let dimension = compute_dimension(positions.iter());
 
-        Piece {
-            dimension: dimension,
+        Ok(Piece {
+            dimension,
             positions: positions.into_boxed_slice(),
-            cost:      cost,
-            distance:  distance,
-            collect:   collect,
-        }
+            cost,
+            distance,
+            collect,
+            button_income: 0,
+            id:            None,
+        })
+    }
+
+    /// Sets the income the piece pays its holder at each collect square.
+    pub fn with_button_income(mut self, button_income: usize) -> Self {
+        self.button_income = button_income;
+        self
+    }
+
+    /// Sets the identifier of the piece.
+    pub(crate) fn with_id(mut self, id: PieceId) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Gets the identifier of the piece, if it has one.
+    ///
+    /// Pieces get identifiers when they are added to a
+    /// [`PieceBoardBuilder`](../piece_board/struct.PieceBoardBuilder.html), and keep them when
+    /// taken and placed.
+    pub fn id(&self) -> Option<PieceId> {
+        self.id
     }
 
     /// A small square piece that is placed on the `TimeBoard`.
@@ -65,11 +152,31 @@ impl Piece {
         self.dimension(transformation).height
     }
 
+    /// Does this piece fit within the given dimension under the given transformation?
+    ///
+    /// Only the piece’s bounding box is considered, not any pieces already on a board.
+    pub fn fits_in(&self, dimension: Dimension, transformation: Transformation) -> bool {
+        let Dimension { width, height } = self.dimension(transformation);
+        width <= dimension.width && height <= dimension.height
+    }
+
     /// Gets the number of positions covered by the piece.
     pub fn size(&self) -> usize {
         self.positions.len()
     }
 
+    /// Gets the number of positions covered by the piece.
+    ///
+    /// This is the same as [`size`](#method.size), not the area of the bounding box.
+    pub fn cell_count(&self) -> usize {
+        self.size()
+    }
+
+    /// Does the piece cover no positions?
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
     /// Gets the cost of this piece.
     pub fn cost(&self) -> usize {
         self.cost
@@ -85,13 +192,234 @@ impl Piece {
         self.collect
     }
 
+    /// Gets the currency that would remain after buying this piece with the given amount.
+    ///
+    /// Returns `None` if the piece costs more than `currency`.
+    pub fn affordability(&self, currency: usize) -> Option<usize> {
+        currency.checked_sub(self.cost)
+    }
+
+    /// Gets the income this piece pays its holder at each collect square.
+    ///
+    /// This defaults to 0, independent of [`collect`](#method.collect).
+    pub fn button_income(&self) -> usize {
+        self.button_income
+    }
+
+    /// Gets the cost of this piece per position covered.
+    ///
+    /// Pieces always cover at least one position, so this is finite.
+    pub fn cost_per_cell(&self) -> f64 {
+        self.cost as f64 / self.size() as f64
+    }
+
+    /// Gets the distance moved for this piece per position covered.
+    pub fn distance_per_cell(&self) -> f64 {
+        self.distance as f64 / self.size() as f64
+    }
+
+    /// Gets the value to collect when holding this piece per position covered.
+    ///
+    /// This uses the [`collect`](#method.collect) value, as its name says, not the
+    /// [`button_income`](#method.button_income) that players are actually paid.
+    pub fn collect_per_cell(&self) -> f64 {
+        self.collect as f64 / self.size() as f64
+    }
+
+    /// Is this piece at least as good as `other` in every respect, and strictly better in one?
+    ///
+    /// A piece is better for covering more positions, paying more
+    /// [`button_income`](#method.button_income), costing less, or moving a shorter distance.
+    /// Shapes and [`collect`](#method.collect) values are not compared, since only button income
+    /// pays the holder.
+    pub fn dominates(&self, other: &Piece) -> bool {
+        let at_least_as_good = self.size() >= other.size() &&
+            self.button_income >= other.button_income &&
+            self.cost <= other.cost &&
+            self.distance <= other.distance;
+
+        let strictly_better = self.size() > other.size() ||
+            self.button_income > other.button_income ||
+            self.cost < other.cost ||
+            self.distance < other.distance;
+
+        at_least_as_good && strictly_better
+    }
+
+    /// Views the untransformed positions of this piece, in sorted order.
+    pub fn raw_positions(&self) -> &[Position] {
+        &self.positions
+    }
+
     /// Gets an iterator over the positions of this piece under the given transformation.
-    pub fn positions(&self, transformation: Transformation) -> Positions {
+    pub fn positions(&self, transformation: Transformation) -> Positions<'_> {
         Positions {
-            raw_positions: (&*self.positions).into_iter(),
+            raw_positions: self.positions.iter(),
             raw_dimension: self.dimension,
-            transformation: transformation,
+            transformation,
+        }
+    }
+
+    /// Collects the positions of this piece under the given transformation.
+    ///
+    /// The order is guaranteed to match [`positions`](#method.positions), which transforms each
+    /// of the [`raw_positions`](#method.raw_positions) in turn.
+    pub fn positions_vec(&self, transformation: Transformation) -> Vec<Position> {
+        self.positions(transformation).collect()
+    }
+
+    /// Draws this piece under the given transformation, in the same format as the `Display`
+    /// implementation for [`QuiltBoard`](../quilt_board/struct.QuiltBoard.html).
+    pub fn to_ascii(&self, transformation: Transformation) -> String {
+        let Dimension { width, height } = self.dimension(transformation);
+        let mut grid = vec![vec!['-'; width]; height];
+
+        for p in self.positions(transformation) {
+            grid[p.y][p.x] = '#';
+        }
+
+        let mut result = String::new();
+
+        for row in grid {
+            result.extend(row);
+            result.push('\n');
         }
+
+        result
+    }
+
+    /// Gets the number of the eight transformations that leave this piece’s shape unchanged.
+    ///
+    /// This ranges from 1 for an asymmetric piece to 8 for a square.
+    pub fn symmetry_order(&self) -> usize {
+        Transformation::all().len() / self.distinct_transformations().len()
+    }
+
+    /// Serializes this piece to JSON, including its untransformed `dimension`.
+    ///
+    /// This is intended for debugging tools; the ordinary `Serialize` format omits the
+    /// dimension, since it is computed from the positions.
+    pub fn to_debug_json(&self) -> serde_json::Value {
+        let mut result = serde_json::to_value(self).expect("Piece serializes to JSON");
+
+        if let Some(fields) = result.as_object_mut() {
+            fields.insert("dimension".to_owned(),
+                          serde_json::to_value(self.dimension).expect("Dimension serializes"));
+        }
+
+        result
+    }
+
+    /// Gets the transformations that produce distinct shapes for this piece.
+    ///
+    /// Of any transformations that produce the same shape, only the first in the order of
+    /// [`Transformation::all`](../position/struct.Transformation.html#method.all) is included.
+    pub fn distinct_transformations(&self) -> Vec<Transformation> {
+        let mut shapes: Vec<Vec<Position>> = Vec::new();
+        let mut result = Vec::new();
+
+        for &transformation in Transformation::all().iter() {
+            let mut shape: Vec<Position> = self.positions(transformation).collect();
+            shape.sort();
+
+            if ! shapes.contains(&shape) {
+                shapes.push(shape);
+                result.push(transformation);
+            }
+        }
+
+        result
+    }
+
+    /// Gets the conventional name of this piece’s shape, if it is a polyomino of up to five
+    /// positions or the seven-position plus.
+    ///
+    /// Shapes are compared up to rotation and flipping, so mirror images such as S and Z
+    /// share a name.
+    pub fn polyomino_name(&self) -> Option<&'static str> {
+        let shape = self.canonical_positions();
+
+        POLYOMINO_NAMES.iter()
+            .find(|&&(art, _)| shape == canonical_from_ascii(art))
+            .map(|&(_, name)| name)
+    }
+
+    /// Gets this piece’s positions under whichever transformation yields the least sorted
+    /// sequence, so that pieces of the same shape have the same canonical positions.
+    pub fn canonical_positions(&self) -> Vec<Position> {
+        Transformation::all().iter()
+            .map(|&transformation| {
+                let mut shape: Vec<Position> = self.positions(transformation).collect();
+                shape.sort();
+                shape
+            })
+            .min()
+            .unwrap()
+    }
+}
+
+/// Shapes with conventional names, drawn with `#` for covered positions.
+const POLYOMINO_NAMES: &[(&str, &str)] = &[
+    ("#",                          "monomino"),
+    ("##",                         "domino"),
+    ("###",                        "I-tromino"),
+    ("##\n#-",                     "L-tromino"),
+    ("####",                       "I-tetromino"),
+    ("##\n##",                     "O-tetromino"),
+    ("###\n-#-",                   "T-tetromino"),
+    ("-##\n##-",                   "S-tetromino"),
+    ("###\n#--",                   "L-tetromino"),
+    ("-##\n##-\n-#-",              "F-pentomino"),
+    ("#####",                      "I-pentomino"),
+    ("####\n#---",                 "L-pentomino"),
+    ("##--\n-###",                 "N-pentomino"),
+    ("##\n##\n#-",                 "P-pentomino"),
+    ("###\n-#-\n-#-",              "T-pentomino"),
+    ("#-#\n###",                   "U-pentomino"),
+    ("#--\n#--\n###",              "V-pentomino"),
+    ("#--\n##-\n-##",              "W-pentomino"),
+    ("-#-\n###\n-#-",              "X-pentomino"),
+    ("####\n-#--",                 "Y-pentomino"),
+    ("##-\n-#-\n-##",              "Z-pentomino"),
+    ("-#-\n-#-\n###\n-#-\n-#-",    "plus"),
+];
+
+/// Parses a shape from `POLYOMINO_NAMES` into canonical positions.
+fn canonical_from_ascii(art: &str) -> Vec<Position> {
+    let mut positions = Vec::new();
+
+    for (y, line) in art.lines().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            if c == '#' {
+                positions.push(Position::new(x, y));
+            }
+        }
+    }
+
+    Piece::new(positions, 0, 0, 0).canonical_positions()
+}
+
+/// Pieces are ordered by cost, then size, distance, collect value, button income, and finally
+/// positions.
+impl Ord for Piece {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        (self.cost, self.size(), self.distance, self.collect, self.button_income, &self.positions)
+            .cmp(&(other.cost, other.size(), other.distance, other.collect, other.button_income,
+                   &other.positions))
+    }
+}
+
+impl PartialEq for Piece {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Piece {}
+
+impl PartialOrd for Piece {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
     }
 }
 
@@ -101,7 +429,15 @@ impl<'de> Deserialize<'de> for Piece {
     {
         #[derive(Deserialize)]
         #[serde(field_identifier, rename_all = "lowercase")]
-        enum Field { Positions, Cost, Distance, Collect, }
+        enum Field {
+            Positions,
+            Cost,
+            Distance,
+            Collect,
+            #[serde(rename = "button_income")]
+            ButtonIncome,
+            Id,
+        }
 
         struct PieceVisitor;
 
@@ -123,7 +459,11 @@ impl<'de> Deserialize<'de> for Piece {
                     .ok_or_else(|| de::Error::invalid_length(2, &self))?;
                 let collect = seq.next_element()?
                     .ok_or_else(|| de::Error::invalid_length(3, &self))?;
-                Ok(Piece::new(positions, cost, distance, collect))
+                let button_income = seq.next_element()?.unwrap_or(0);
+                let id = seq.next_element()?.unwrap_or(None);
+                Piece::try_new(positions, cost, distance, collect)
+                    .map(|piece| Piece { id, ..piece.with_button_income(button_income) })
+                    .map_err(de::Error::custom)
             }
 
             fn visit_map<V>(self, mut map: V) -> Result<Piece, V::Error>
@@ -133,6 +473,8 @@ impl<'de> Deserialize<'de> for Piece {
                 let mut cost = None;
                 let mut distance = None;
                 let mut collect = None;
+                let mut button_income = None;
+                let mut id = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -160,6 +502,18 @@ impl<'de> Deserialize<'de> for Piece {
                             }
                             collect = Some(map.next_value()?);
                         }
+                        Field::ButtonIncome => {
+                            if button_income.is_some() {
+                                return Err(de::Error::duplicate_field("button_income"));
+                            }
+                            button_income = Some(map.next_value()?);
+                        }
+                        Field::Id => {
+                            if id.is_some() {
+                                return Err(de::Error::duplicate_field("id"));
+                            }
+                            id = Some(map.next_value()?);
+                        }
                     }
                 }
 
@@ -167,16 +521,77 @@ impl<'de> Deserialize<'de> for Piece {
                 let cost      = cost.ok_or_else(|| de::Error::missing_field("cost"))?;
                 let distance  = distance.ok_or_else(|| de::Error::missing_field("distance"))?;
                 let collect   = collect.ok_or_else(|| de::Error::missing_field("collect"))?;
+                let button_income = button_income.unwrap_or(0);
+                let id        = id.unwrap_or(None);
 
-                Ok(Piece::new(positions, cost, distance, collect))
+                Piece::try_new(positions, cost, distance, collect)
+                    .map(|piece| Piece { id, ..piece.with_button_income(button_income) })
+                    .map_err(de::Error::custom)
             }
         }
 
-        const FIELDS: &'static [&'static str] = &["positions", "cost", "distance", "collect"];
+        const FIELDS: &[&str] = &["positions", "cost", "distance", "collect", "button_income",
+                                   "id"];
         deserializer.deserialize_struct("Piece", FIELDS, PieceVisitor)
     }
 }
 
+/// Do the two pieces overlap when placed at the given positions under the given
+/// transformations?
+pub fn overlaps(a: &Piece, pa: Position, ta: Transformation,
+                b: &Piece, pb: Position, tb: Transformation)
+                -> bool
+{
+    if ! a.dimension(ta).intersects_at(pa, b.dimension(tb), pb) {
+        return false;
+    }
+
+    let cells: Vec<Position> = a.positions(ta).map(|p| p.translate(pa)).collect();
+    b.positions(tb).any(|p| cells.contains(&p.translate(pb)))
+}
+
+/// Gets the board positions covered by the piece when placed at the given position under the
+/// given transformation, sorted.
+///
+/// Placements with equal footprints are indistinguishable on the board, even if their
+/// transformations differ.
+pub fn footprint(piece: &Piece, position: Position, transformation: Transformation)
+                 -> Vec<Position>
+{
+    let mut result: Vec<Position> = piece.positions(transformation)
+        .map(|p| p.translate(position))
+        .collect();
+    result.sort();
+    result
+}
+
+/// Are the given positions orthogonally connected?
+fn is_connected(positions: &[Position]) -> bool {
+    let adjacent = |p: Position, q: Position| {
+        (p.x == q.x && (p.y + 1 == q.y || q.y + 1 == p.y)) ||
+            (p.y == q.y && (p.x + 1 == q.x || q.x + 1 == p.x))
+    };
+
+    let mut reached = vec![false; positions.len()];
+    let mut stack = Vec::new();
+
+    if ! positions.is_empty() {
+        reached[0] = true;
+        stack.push(positions[0]);
+    }
+
+    while let Some(p) = stack.pop() {
+        for (i, &q) in positions.iter().enumerate() {
+            if ! reached[i] && adjacent(p, q) {
+                reached[i] = true;
+                stack.push(q);
+            }
+        }
+    }
+
+    reached.into_iter().all(|b| b)
+}
+
 /// Computes the maximum dimension required to hold the given positions.
 fn compute_dimension<'a, I>(positions: I) -> Dimension
     where I: Iterator<Item = &'a Position>
@@ -262,6 +677,7 @@ pub mod examples {
     pub fn piece2() -> Piece {
         Piece::new(vec![pos(0, 0), pos(1, 0), pos(1, 1), pos(2, 1), pos(1, 2), pos(2, 2)],
                    8, 6, 3)
+            .with_button_income(3)
     }
 
     /// A piece.
@@ -288,6 +704,7 @@ pub mod examples {
         Piece::new(vec![pos(1, 0), pos(1, 1), pos(0, 2), pos(1, 2),
                         pos(2, 2), pos(1, 3), pos(1, 4)],
                    1, 4, 1)
+            .with_button_income(1)
     }
 }
 
@@ -366,4 +783,251 @@ mod test {
         assert_eq!(positions.next(), Some(pos(2, 1)));
         assert_eq!(positions.next(), None);
     }
+
+    #[test]
+    fn cell_count() {
+        let piece = Piece::single_position();
+        assert_eq!(piece.cell_count(), 1);
+        assert!(! piece.is_empty());
+
+        assert_eq!(examples::piece0().cell_count(), 4);
+    }
+
+    #[test]
+    fn reject_empty_piece() {
+        assert_eq!(Piece::try_new(vec![], 1, 1, 0), Err(PlayerError::EmptyPiece));
+
+        let json = br#"{"positions": [], "cost": 1, "distance": 1, "collect": 0}"#;
+        let error = serde_json::from_slice::<Piece>(json).unwrap_err();
+        assert!(error.to_string().contains("Piece has no positions"));
+
+        let json = br#"[[], 1, 1, 0]"#;
+        assert!(serde_json::from_slice::<Piece>(json).is_err());
+    }
+
+    #[test]
+    fn debug_json_has_dimension() {
+        let piece = examples::piece0();
+        let json = piece.to_debug_json();
+        let dimension = piece.dimension(Transformation::identity());
+
+        assert_eq!(json["dimension"]["width"], dimension.width);
+        assert_eq!(json["dimension"]["height"], dimension.height);
+        assert_eq!(json["cost"], piece.cost());
+
+        let canonical = serde_json::to_value(&piece).unwrap();
+        assert!(canonical.get("dimension").is_none());
+    }
+
+    #[test]
+    fn reject_disconnected_piece() {
+        assert_eq!(Piece::try_new(vec![pos(0, 0), pos(1, 1)], 1, 1, 0),
+                   Err(PlayerError::DisconnectedPiece));
+        assert_eq!(Piece::try_new(vec![pos(0, 0), pos(2, 0), pos(1, 0)], 1, 1, 0),
+                   Ok(Piece::new(vec![pos(0, 0), pos(1, 0), pos(2, 0)], 1, 1, 0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Piece has no positions")]
+    fn new_empty_piece_panics() {
+        Piece::new(vec![], 1, 1, 0);
+    }
+
+    #[test]
+    fn overlapping_pieces() {
+        let t = Transformation::identity();
+        let piece0 = examples::piece0();
+        let single = Piece::single_position();
+
+        assert!(! overlaps(&piece0, pos(0, 0), t, &single, pos(2, 0), t));
+        assert!(! overlaps(&piece0, pos(0, 0), t, &single, pos(0, 1), t));
+        assert!(overlaps(&piece0, pos(0, 0), t, &single, pos(1, 1), t));
+
+        let flipped = Transformation::new(NoRotation, Horizontal);
+        assert!(! overlaps(&piece0, pos(0, 0), t, &piece0, pos(2, 0), flipped));
+        assert!(overlaps(&piece0, pos(0, 0), t, &piece0, pos(1, 0), flipped));
+    }
+
+    #[test]
+    fn per_cell_metrics() {
+        let piece = examples::piece2();
+
+        assert_eq!(piece.cost_per_cell(), 8.0 / 6.0);
+        assert_eq!(piece.distance_per_cell(), 1.0);
+        assert_eq!(piece.collect_per_cell(), 0.5);
+        assert_eq!(Piece::single_position().cost_per_cell(), 0.0);
+    }
+
+    #[test]
+    fn symmetry_order() {
+        assert_eq!(Piece::single_position().symmetry_order(), 8);
+        assert_eq!(examples::piece0().symmetry_order(), 1);
+        assert_eq!(examples::piece1().symmetry_order(), 2);
+        assert_eq!(examples::piece4().symmetry_order(), 4);
+    }
+
+    #[test]
+    fn to_ascii() {
+        let piece = examples::piece0();
+
+        assert_eq!(piece.to_ascii(Transformation::identity()),
+                   "##\n\
+                    -#\n\
+                    -#\n");
+        assert_eq!(piece.to_ascii(Transformation::new(Clockwise90, Identity)),
+                   "--#\n\
+                    ###\n");
+    }
+
+    #[test]
+    fn piece_ordering() {
+        use self::examples::*;
+
+        let mut pieces = vec![piece0(), piece1(), piece2(), piece3(), piece4()];
+        pieces.sort();
+        assert_eq!(pieces, vec![piece3(), piece1(), piece4(), piece0(), piece2()]);
+
+        assert!(Piece::single_position() < piece3());
+        assert!(Piece::new(vec![pos(0, 0)], 1, 1, 0) < Piece::new(vec![pos(0, 0)], 1, 2, 0));
+    }
+
+    #[test]
+    fn raw_positions() {
+        let piece = examples::piece2();
+        let positions: Vec<Position> = piece.positions(Transformation::identity()).collect();
+        assert_eq!(piece.raw_positions(), &*positions);
+    }
+
+    #[test]
+    fn distinct_transformations() {
+        assert_eq!(Piece::single_position().distinct_transformations(),
+                   vec![Transformation::identity()]);
+        assert_eq!(examples::piece0().distinct_transformations().len(), 8);
+        assert_eq!(examples::piece4().distinct_transformations().len(), 2);
+    }
+
+    #[test]
+    fn polyomino_names() {
+        assert_eq!(examples::piece4().polyomino_name(), Some("plus"));
+        assert_eq!(examples::piece3().polyomino_name(), Some("L-tromino"));
+        assert_eq!(examples::piece0().polyomino_name(), Some("L-tetromino"));
+        assert_eq!(examples::piece1().polyomino_name(), None);
+        assert_eq!(Piece::single_position().polyomino_name(), Some("monomino"));
+    }
+
+    #[test]
+    fn new_checked_reports_normalization() {
+        let (piece, changed) = Piece::new_checked(vec![pos(0, 0), pos(1, 0), pos(0, 0)], 1, 1, 0);
+        assert!(changed);
+        assert_eq!(piece.size(), 2);
+        assert_eq!(piece.raw_positions(), &[pos(0, 0), pos(1, 0)]);
+
+        let (_, changed) = Piece::new_checked(vec![pos(1, 0), pos(0, 0)], 1, 1, 0);
+        assert!(changed);
+
+        let (piece, changed) = Piece::new_checked(vec![pos(0, 0), pos(1, 0)], 1, 1, 0);
+        assert!(! changed);
+        assert_eq!(piece, Piece::new(vec![pos(0, 0), pos(1, 0)], 1, 1, 0));
+    }
+
+    #[test]
+    fn footprint_ignores_equivalent_transformations() {
+        let piece = examples::piece4();
+        let flipped = Transformation::new(NoRotation, Horizontal);
+        let turned = Transformation::new(Clockwise180, Identity);
+
+        assert_eq!(footprint(&piece, pos(2, 3), Transformation::identity()),
+                   footprint(&piece, pos(2, 3), flipped));
+        assert_eq!(footprint(&piece, pos(2, 3), Transformation::identity()),
+                   footprint(&piece, pos(2, 3), turned));
+        assert_ne!(footprint(&piece, pos(2, 3), Transformation::identity()),
+                   footprint(&piece, pos(2, 3), Transformation::new(Clockwise90, Identity)));
+        assert_eq!(footprint(&examples::piece3(), pos(1, 1), Transformation::identity()),
+                   vec![pos(1, 2), pos(2, 1), pos(2, 2)]);
+    }
+
+    #[test]
+    fn dominates() {
+        let shape = vec![pos(0, 0), pos(1, 0), pos(1, 1)];
+        let cheap = Piece::new(shape.clone(), 1, 2, 0).with_button_income(2);
+        let expensive = Piece::new(shape.clone(), 4, 2, 0);
+
+        assert!(cheap.dominates(&expensive));
+        assert!(! expensive.dominates(&cheap));
+        assert!(! cheap.dominates(&cheap));
+
+        // Only button income counts, not collect.
+        let income = Piece::new(shape.clone(), 1, 2, 0).with_button_income(1);
+        let collect = Piece::new(shape, 1, 2, 5);
+        assert!(income.dominates(&collect));
+        assert!(! collect.dominates(&income));
+
+        // piece3 is cheaper than piece0, but smaller.
+        assert!(! examples::piece3().dominates(&examples::piece0()));
+    }
+
+    #[test]
+    fn affordability() {
+        let piece = examples::piece0();
+        assert_eq!(piece.cost(), 2);
+        assert_eq!(piece.affordability(5), Some(3));
+        assert_eq!(piece.affordability(2), Some(0));
+        assert_eq!(piece.affordability(1), None);
+    }
+
+    #[test]
+    fn deserialize_button_income() {
+        let without = br#"{"positions": [{"x": 0, "y": 0}], "cost": 1, "distance": 1, "collect": 2}"#;
+        let piece: Piece = serde_json::from_slice(without).unwrap();
+        assert_eq!(piece.collect(), 2);
+        assert_eq!(piece.button_income(), 0);
+
+        let with = br#"{"positions": [{"x": 0, "y": 0}], "cost": 1, "distance": 1, "collect": 2,
+                        "button_income": 3}"#;
+        let piece: Piece = serde_json::from_slice(with).unwrap();
+        assert_eq!(piece.collect(), 2);
+        assert_eq!(piece.button_income(), 3);
+
+        let round_trip: Piece = serde_json::from_str(&serde_json::to_string(&piece).unwrap())
+            .unwrap();
+        assert_eq!(round_trip, piece);
+    }
+
+    #[test]
+    fn deserialize_compact_positions() {
+        let compact = br#"{"positions": [[0, 0], [1, 0], [1, 1], [1, 2]],
+                           "cost": 2, "distance": 1, "collect": 0}"#;
+        let verbose = br#"{"positions": [{"x": 0, "y": 0}, {"x": 1, "y": 0},
+                                         {"x": 1, "y": 1}, {"x": 1, "y": 2}],
+                           "cost": 2, "distance": 1, "collect": 0}"#;
+
+        let compact: Piece = serde_json::from_slice(compact).unwrap();
+        let verbose: Piece = serde_json::from_slice(verbose).unwrap();
+        assert_eq!(compact, examples::piece0());
+        assert_eq!(verbose, examples::piece0());
+    }
+
+    #[test]
+    fn positions_vec_matches_iterator() {
+        let piece = examples::piece1();
+
+        for &transformation in &Transformation::all() {
+            assert_eq!(piece.positions_vec(transformation),
+                       piece.positions(transformation).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn fits_in_depends_on_rotation() {
+        use position::{Rotation, Flip};
+
+        // piece1 is 3 wide and 4 tall.
+        let piece = examples::piece1();
+        let board = Dimension::new(3, 4);
+        let rotated = Transformation::new(Rotation::Clockwise90, Flip::Identity);
+
+        assert!(piece.fits_in(board, Transformation::identity()));
+        assert!(! piece.fits_in(board, rotated));
+        assert!(piece.fits_in(board.transpose(), rotated));
+    }
 }
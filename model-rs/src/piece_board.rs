@@ -1,17 +1,27 @@
 //! The queue of pieces to choose from.
 
-use std::collections::{vec_deque, VecDeque};
+use std::cmp;
+use std::collections::{vec_deque, HashMap, VecDeque};
 use std::default::Default;
+use std::iter;
 
 use rand;
+use serde::de;
 use serde_json;
 
-use piece::Piece;
+use piece::{Piece, PieceId};
+use position::Position;
 
 use result::{QResult, PlayerError};
+use util::shuffle;
 
 /// The default set of pieces, serialized.
-const PIECES_JSON: &'static [u8] = include_bytes!("../data/pieces.json");
+const PIECES_JSON: &[u8] = include_bytes!("../data/pieces.json");
+
+/// Deserializes the default set of pieces.
+pub fn default_pieces() -> Vec<Piece> {
+    serde_json::from_slice(PIECES_JSON).expect("default pieces are valid")
+}
 
 /// The default depth at which we can take pieces (0-based).
 const DEFAULT_DEPTH: usize = 2;
@@ -20,21 +30,27 @@ const DEFAULT_DEPTH: usize = 2;
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct PieceBoardBuilder {
     piece_queue: VecDeque<Piece>,
+    weights:     Vec<usize>,
     depth:       usize,
+    next_id:     usize,
 }
 
+/// The weight given to pieces added without an explicit weight.
+const DEFAULT_WEIGHT: usize = 1;
+
 impl PieceBoardBuilder {
     /// Will build a `PieceBoard` with the default depth and default set of pieces.
     pub fn new() -> Self {
-        let result = Self::empty();
-        result.extend_from_slice(PIECES_JSON).unwrap()
+        Self::empty().extend(default_pieces())
     }
 
     /// Will build a `PieceBoard` with the default depth and no pieces.
     pub fn empty() -> Self {
         PieceBoardBuilder {
             piece_queue: VecDeque::new(),
+            weights:     Vec::new(),
             depth:       DEFAULT_DEPTH,
+            next_id:     0,
         }
     }
 
@@ -44,32 +60,131 @@ impl PieceBoardBuilder {
         self
     }
 
+    /// Gives the piece the next unused identifier.
+    fn identify(&mut self, piece: Piece) -> Piece {
+        let id = PieceId::new(self.next_id);
+        self.next_id += 1;
+        piece.with_id(id)
+    }
+
     /// Adds the given sequence of pieces to the piece queue.
     pub fn extend<I>(mut self, pieces: I) -> Self
         where I: IntoIterator<Item = Piece>
     {
-        self.piece_queue.extend(pieces);
+        for piece in pieces {
+            let piece = self.identify(piece);
+            self.piece_queue.push_back(piece);
+            self.weights.push(DEFAULT_WEIGHT);
+        }
         self
     }
 
+    /// Adds the given sequence of pieces to the piece queue, each with a weight for
+    /// [`build_weighted`](#method.build_weighted).
+    ///
+    /// Pieces added by other means have weight 1. Weights whose total overflows are reported
+    /// by `build_weighted`.
+    pub fn extend_weighted<I>(mut self, weighted: I) -> Self
+        where I: IntoIterator<Item = (Piece, usize)>
+    {
+        for (piece, weight) in weighted {
+            let piece = self.identify(piece);
+            self.piece_queue.push_back(piece);
+            self.weights.push(weight);
+        }
+        self
+    }
+
+    /// Inserts a piece into the piece queue at the given index, or at the end if `index` is
+    /// past the end.
+    pub fn insert_at(mut self, index: usize, piece: Piece) -> Self {
+        let index = cmp::min(index, self.piece_queue.len());
+        let piece = self.identify(piece);
+        self.piece_queue.insert(index, piece);
+        self.weights.insert(index, DEFAULT_WEIGHT);
+        self
+    }
+
+    /// Checks whether the builder has no pieces.
+    pub fn is_empty(&self) -> bool {
+        self.piece_queue.is_empty()
+    }
+
     /// Remove all pieces from the builder.
     pub fn clear(mut self) -> Self {
         self.piece_queue = VecDeque::new();
+        self.weights = Vec::new();
         self
     }
 
     /// Deserializes pieces from a `&[u8]` of JSON, adding to the piece queue.
+    ///
+    /// Each piece must be non-empty and connected. If any piece is invalid, the error message
+    /// gives its index in the array.
     pub fn extend_from_slice(self, pieces: &[u8]) -> serde_json::Result<Self> {
-        let pieces: Vec<Piece> = serde_json::from_slice(pieces)?;
+        let values: Vec<serde_json::Value> = serde_json::from_slice(pieces)?;
+        let mut pieces = Vec::with_capacity(values.len());
+
+        for (i, value) in values.into_iter().enumerate() {
+            let piece = serde_json::from_value(value).map_err(|e| {
+                <serde_json::Error as de::Error>::custom(format_args!("piece {}: {}", i, e))
+            })?;
+            pieces.push(piece);
+        }
+
         Ok(self.extend(pieces))
     }
 
     /// Builds the `PieceBoard`, shuffling the pieces.
     pub fn build(mut self) -> PieceBoard {
-        shuffle(&mut rand::thread_rng(), &mut self.piece_queue);
+        shuffle(&mut rand::thread_rng(), self.piece_queue.make_contiguous());
         self.build_in_order()
     }
 
+    /// Builds the `PieceBoard`, ordering the pieces by weighted sampling without replacement.
+    ///
+    /// Each position in the queue is filled by drawing one of the remaining pieces with
+    /// probability proportional to its weight. Pieces with weight zero are never drawn, and
+    /// instead follow all the others in their original order.
+    ///
+    /// Returns `Err(PlayerError::WeightOverflow)` if the weights add up to more than `usize`
+    /// can hold.
+    pub fn build_weighted<R: rand::Rng>(self, rng: &mut R) -> QResult<PieceBoard> {
+        use rand::distributions::{IndependentSample, Range};
+
+        let mut remaining: Vec<(Piece, usize)> =
+            self.piece_queue.into_iter().zip(self.weights).collect();
+        let mut piece_queue = VecDeque::with_capacity(remaining.len());
+        let mut total = remaining.iter()
+            .try_fold(0usize, |total, &(_, weight)| total.checked_add(weight))
+            .ok_or(PlayerError::WeightOverflow)?;
+
+        while total > 0 {
+            let mut choice = Range::new(0, total).ind_sample(rng);
+            let index = remaining.iter()
+                .position(|&(_, weight)| {
+                    if choice < weight {
+                        true
+                    } else {
+                        choice -= weight;
+                        false
+                    }
+                })
+                .unwrap();
+
+            let (piece, weight) = remaining.remove(index);
+            total -= weight;
+            piece_queue.push_back(piece);
+        }
+
+        piece_queue.extend(remaining.into_iter().map(|(piece, _)| piece));
+
+        Ok(PieceBoard {
+            piece_queue,
+            depth: self.depth,
+        })
+    }
+
     /// Builds the `PieceBoard` without shuffling the pieces.
     pub fn build_in_order(self) -> PieceBoard {
         PieceBoard {
@@ -79,21 +194,10 @@ impl PieceBoardBuilder {
     }
 }
 
-/// [Fisher-Yates shuffle](https://en.wikipedia.org/wiki/Fisher%E2%80%93Yates_shuffle).
-fn shuffle<R: rand::Rng, T>(rng: &mut R, vd: &mut VecDeque<T>) {
-    use rand::distributions::{IndependentSample, Range};
-
-    for i in (1 .. vd.len()).rev() {
-        let range = Range::new(0, i);
-        let j = range.ind_sample(rng);
-        vd.swap(i, j);
-    }
-}
-
 /// The queue of [`Piece`](../piece/struct.Piece.html)s to be taken.
 ///
 /// Configure and construct with [`PieceBoardBuilder`](struct.PieceBoardBuilder.html).
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct PieceBoard {
     piece_queue: VecDeque<Piece>,
     depth: usize,
@@ -115,24 +219,79 @@ impl PieceBoard {
         self.depth
     }
 
+    /// Changes the allowed take depth.
+    pub fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
+    }
+
     /// Gets an iterator over the available pieces in order.
-    pub fn pieces(&self) -> Pieces {
+    pub fn pieces(&self) -> Pieces<'_> {
         Pieces(self.piece_queue.iter())
     }
 
-    /// Takes the `depth`th piece, if possible.
-    pub fn take(&mut self, depth: usize) -> QResult<Piece> {
+    /// Gets an iterator over the pieces that may be taken, in order.
+    pub fn takeable(&self) -> iter::Take<Pieces<'_>> {
+        self.pieces().take(self.depth + 1)
+    }
+
+    /// Finds the takeable piece that the given function scores highest, along with its depth.
+    ///
+    /// Ties go to the shallowest piece. Returns `None` if there are no pieces.
+    pub fn best_takeable_by<F>(&self, score: F) -> Option<(usize, &Piece)>
+        where F: Fn(&Piece) -> i64
+    {
+        let mut best: Option<(i64, usize, &Piece)> = None;
+
+        for (depth, piece) in self.takeable().enumerate() {
+            let piece_score = score(piece);
+
+            if best.is_none_or(|(best_score, _, _)| piece_score > best_score) {
+                best = Some((piece_score, depth, piece));
+            }
+        }
+
+        best.map(|(_, depth, piece)| (depth, piece))
+    }
+
+    /// Counts the available pieces of each shape, keyed by
+    /// [canonical positions](../piece/struct.Piece.html#method.canonical_positions).
+    pub fn shape_histogram(&self) -> HashMap<Vec<Position>, usize> {
+        let mut result = HashMap::new();
+
+        for piece in self.pieces() {
+            *result.entry(piece.canonical_positions()).or_insert(0) += 1;
+        }
+
+        result
+    }
+
+    /// Views the `depth`th piece without taking it, if it could be taken.
+    pub fn peek(&self, depth: usize) -> QResult<&Piece> {
         if depth > self.depth {
             Err(PlayerError::TakeOverDepth)
-        } else if depth >= self.piece_queue.len() {
-            Err(PlayerError::OutOfPieces)
         } else {
-            for _ in 0..depth {
-                let piece = self.piece_queue.pop_front().unwrap();
-                self.piece_queue.push_back(piece);
-            }
-            let result = self.piece_queue.pop_front().unwrap();
-            Ok(result)
+            self.piece_queue.get(depth).ok_or(PlayerError::OutOfPieces)
+        }
+    }
+
+    /// Takes the `depth`th piece, if possible.
+    pub fn take(&mut self, depth: usize) -> QResult<Piece> {
+        self.peek(depth)?;
+
+        for _ in 0..depth {
+            let piece = self.piece_queue.pop_front().unwrap();
+            self.piece_queue.push_back(piece);
+        }
+
+        Ok(self.piece_queue.pop_front().unwrap())
+    }
+
+    /// Moves the front piece to the back of the queue, skipping it.
+    ///
+    /// Does nothing if the queue is empty.
+    pub fn rotate_front_to_back(&mut self) {
+        if let Some(piece) = self.piece_queue.pop_front() {
+            self.piece_queue.push_back(piece);
         }
     }
 }
@@ -201,6 +360,34 @@ mod test {
         assert_eq!(board.len(), 4);
     }
 
+    #[test]
+    fn extend_from_slice_rejects_disconnected() {
+        let json = br#"[
+          {"positions": [{"x": 0, "y": 0}, {"x": 1, "y": 0}], "cost": 1, "distance": 1, "collect": 0},
+          {"positions": [{"x": 0, "y": 0}, {"x": 2, "y": 0}], "cost": 1, "distance": 1, "collect": 0}
+        ]"#;
+
+        let error = PieceBoardBuilder::empty().extend_from_slice(json).unwrap_err();
+        assert_eq!(error.to_string(), "piece 1: Piece positions are not connected");
+    }
+
+    #[test]
+    fn insert_at() {
+        let mut board = PieceBoardBuilder::empty()
+            .extend(pieces())
+            .insert_at(1, examples::piece0())
+            .insert_at(10, Piece::single_position())
+            .build_in_order();
+
+        assert_eq!(board.take(0), Ok(examples::piece1()));
+        assert_eq!(board.take(0), Ok(examples::piece0()));
+        assert_eq!(board.take(0), Ok(examples::piece2()));
+        assert_eq!(board.take(0), Ok(examples::piece3()));
+        assert_eq!(board.take(0), Ok(examples::piece4()));
+        assert_eq!(board.take(0), Ok(Piece::single_position()));
+        assert!(board.is_empty());
+    }
+
     #[test]
     fn take_over_depth_error() {
         let mut board = PieceBoardBuilder::empty()
@@ -209,6 +396,58 @@ mod test {
         assert_eq!(board.take(5), Err(PlayerError::TakeOverDepth));
     }
 
+    #[test]
+    fn takeable_pieces() {
+        let board = PieceBoardBuilder::empty()
+            .extend(pieces())
+            .build_in_order();
+
+        assert_eq!(board.takeable().cloned().collect::<Vec<_>>(),
+                   vec![examples::piece1(), examples::piece2(), examples::piece3()]);
+    }
+
+    #[test]
+    fn best_takeable_by() {
+        let board = PieceBoardBuilder::empty()
+            .extend(vec![examples::piece2(), examples::piece3(), examples::piece1(),
+                         examples::piece0()])
+            .build_in_order();
+
+        // piece3 and piece1 tie as the cheapest, so the shallower one wins.
+        assert_eq!(board.best_takeable_by(|piece| -(piece.cost() as i64)),
+                   Some((1, &examples::piece3())));
+        assert_eq!(board.best_takeable_by(|piece| piece.size() as i64),
+                   Some((0, &examples::piece2())));
+
+        let empty = PieceBoardBuilder::empty().build_in_order();
+        assert_eq!(empty.best_takeable_by(|piece| piece.size() as i64), None);
+    }
+
+    #[test]
+    fn peek_does_not_take() {
+        let board = PieceBoardBuilder::empty()
+            .extend(pieces())
+            .build_in_order();
+
+        assert_eq!(board.peek(1), Ok(&examples::piece2()));
+        assert_eq!(board.peek(3), Err(PlayerError::TakeOverDepth));
+        assert_eq!(board.len(), 4);
+    }
+
+    #[test]
+    fn set_depth() {
+        let mut board = PieceBoardBuilder::empty()
+            .extend(pieces())
+            .build_in_order();
+
+        assert_eq!(board.peek(2), Ok(&examples::piece3()));
+        board.set_depth(1);
+        assert_eq!(board.depth(), 1);
+        assert_eq!(board.take(2), Err(PlayerError::TakeOverDepth));
+        assert_eq!(board.takeable().count(), 2);
+        assert_eq!(board.take(1), Ok(examples::piece2()));
+    }
+
     #[test]
     fn take_0_repeatedly() {
         let mut board = PieceBoardBuilder::empty()
@@ -238,4 +477,104 @@ mod test {
         assert_eq!(board.take(0), Ok(examples::piece3()));
         assert_eq!(board.take(0), Err(PlayerError::OutOfPieces));
     }
+
+    #[test]
+    fn rotate_front_to_back() {
+        let mut board = PieceBoardBuilder::empty()
+            .extend(pieces())
+            .build_in_order();
+
+        board.rotate_front_to_back();
+        assert_eq!(board.pieces().next(), Some(&examples::piece2()));
+        assert_eq!(board.pieces().next_back(), Some(&examples::piece1()));
+        assert_eq!(board.len(), 4);
+        assert_eq!(board.depth(), DEFAULT_DEPTH);
+
+        let mut empty = PieceBoardBuilder::empty().build_in_order();
+        empty.rotate_front_to_back();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn build_weighted_favors_heavy_pieces() {
+        use rand::{SeedableRng, StdRng};
+
+        let mut rng = StdRng::from_seed(&[3, 5, 7][..]);
+        let mut heavy_first = 0;
+
+        for _ in 0..100 {
+            let board = PieceBoardBuilder::empty()
+                .extend_weighted(vec![(examples::piece1(), 0),
+                                      (examples::piece2(), 1),
+                                      (examples::piece3(), 1),
+                                      (examples::piece4(), 50)])
+                .build_weighted(&mut rng)
+                .unwrap();
+
+            assert_eq!(board.len(), 4);
+            assert_eq!(board.pieces().next_back(), Some(&examples::piece1()));
+
+            if board.pieces().next() == Some(&examples::piece4()) {
+                heavy_first += 1;
+            }
+        }
+
+        assert!(heavy_first > 80);
+    }
+
+    #[test]
+    fn build_weighted_rejects_overflowing_weights() {
+        use rand::{SeedableRng, StdRng};
+
+        let mut rng = StdRng::from_seed(&[3, 5, 7][..]);
+        let result = PieceBoardBuilder::empty()
+            .extend_weighted(vec![(examples::piece1(), usize::MAX),
+                                  (examples::piece2(), 1)])
+            .build_weighted(&mut rng);
+
+        assert_eq!(result, Err(PlayerError::WeightOverflow));
+    }
+
+    #[test]
+    fn default_pieces_round_trip() {
+        let pieces = default_pieces();
+        assert!(! pieces.is_empty());
+
+        let board = PieceBoardBuilder::empty()
+            .extend(pieces.clone())
+            .build_in_order();
+        assert_eq!(board.pieces().cloned().collect::<Vec<_>>(), pieces);
+        assert_eq!(board, PieceBoardBuilder::new().build_in_order());
+    }
+
+    #[test]
+    fn shape_histogram_counts_same_shapes() {
+        use position::{Rotation, Flip, Transformation};
+
+        let turned = Transformation::new(Rotation::Clockwise90, Flip::Horizontal);
+        let same_shape = Piece::new(examples::piece0().positions(turned).collect(), 5, 5, 1);
+
+        let board = PieceBoardBuilder::empty()
+            .extend(vec![examples::piece0(), same_shape, examples::piece3()])
+            .build();
+        let histogram = board.shape_histogram();
+
+        assert_eq!(histogram.len(), 2);
+        assert_eq!(histogram[&examples::piece0().canonical_positions()], 2);
+        assert_eq!(histogram[&examples::piece3().canonical_positions()], 1);
+    }
+
+    #[test]
+    fn taken_piece_keeps_id() {
+        let mut board = PieceBoardBuilder::empty()
+            .extend(pieces())
+            .build();
+
+        let ids: Vec<Option<PieceId>> = board.pieces().map(Piece::id).collect();
+        assert!(ids.iter().all(Option::is_some));
+
+        let taken = board.take(1).unwrap();
+        assert_eq!(taken.id(), ids[1]);
+        assert!(board.pieces().all(|piece| piece.id() != taken.id()));
+    }
 }
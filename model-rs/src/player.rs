@@ -1,11 +1,15 @@
 //! Represents players of the game.
 
+use std::cmp;
 use std::default::Default;
 use std::slice;
 use rand;
 
-use position::Dimension;
+use piece::Piece;
+use position::{Dimension, Position, Transformation};
 use quilt_board::{self, QuiltBoard};
+use result::{QResult, PlayerError};
+use util::{saturating_i64, shuffle};
 
 /// A game player.
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
@@ -17,7 +21,19 @@ pub const DEFAULT_NPLAYERS: usize = 2;
 /// The default amount of money each player starts with (5).
 pub const DEFAULT_STARTING_CURRENCY: usize = 5;
 
+/// The most money a player can hold; earning more has no effect.
+///
+/// This is the largest `i64`, so that currency always converts to a positive score.
+pub const MAX_CURRENCY: usize = i64::MAX as usize;
+
 impl Player {
+    /// The player identified by the given `usize`.
+    ///
+    /// This is the inverse of [`to_usize`](#method.to_usize).
+    pub fn from_usize(n: usize) -> Self {
+        Player(n)
+    }
+
     /// A `usize` identifying the given player.
     ///
     /// Players are numbered starting at 0, so they are suitable as array indices.
@@ -54,6 +70,26 @@ impl PlayOrder {
         PlayOrder(stack)
     }
 
+    /// Creates a new `PlayOrder` with the given players, who will play in the order given.
+    ///
+    /// # Errors
+    ///
+    /// Panics unless the players are distinct and numbered below the number of players.
+    pub fn from_players(mut players: Vec<Player>) -> Self {
+        let nplayers = players.len();
+        let mut seen = vec![false; nplayers];
+
+        for player in &players {
+            let i = player.to_usize();
+            assert!(i < nplayers, "Player number out of range");
+            assert!(! seen[i], "Players must be distinct");
+            seen[i] = true;
+        }
+
+        players.reverse();
+        PlayOrder(players)
+    }
+
     /// Is the given `PlayOrder` empty?
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
@@ -65,10 +101,15 @@ impl PlayOrder {
     }
 
     /// Gets an iterator over the players in play order.
-    pub fn players(&self) -> Players {
+    pub fn players(&self) -> Players<'_> {
         Players(self.0.iter())
     }
 
+    /// Finds how many players go before the given player, if they are in the play order.
+    pub fn position_of(&self, player: Player) -> Option<usize> {
+        self.players().position(|p| *p == player)
+    }
+
     /// Pushes a player to go next in the play order.
     pub fn push(&mut self, player: Player) {
         self.0.push(player)
@@ -78,16 +119,17 @@ impl PlayOrder {
     pub fn pop(&mut self) -> Option<Player> {
         self.0.pop()
     }
-}
 
-/// [Fisher-Yates shuffle](https://en.wikipedia.org/wiki/Fisher%E2%80%93Yates_shuffle).
-fn shuffle<R: rand::Rng, T>(rng: &mut R, v: &mut Vec<T>) {
-    use rand::distributions::{IndependentSample, Range};
+    /// Views the next player in the play order without removing them.
+    pub fn current(&self) -> Option<&Player> {
+        self.0.last()
+    }
 
-    for i in (1 .. v.len()).rev() {
-        let range = Range::new(0, i);
-        let j = range.ind_sample(rng);
-        v.swap(i, j);
+    /// Moves the next player to the end of the play order.
+    pub fn rotate(&mut self) {
+        if let Some(player) = self.0.pop() {
+            self.0.insert(0, player);
+        }
     }
 }
 
@@ -134,7 +176,7 @@ impl<'a> DoubleEndedIterator for Players<'a> {
 }
 
 /// The state associated with one player.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PlayerState {
     /// The player’s quilt board.
     quilt_board: QuiltBoard,
@@ -142,16 +184,142 @@ pub struct PlayerState {
     currency:    usize,
     /// Any bonus points earned by the player.
     bonus:       usize,
+    /// The amount collected per collection, from the pieces on the quilt board.
+    income:      usize,
+    /// The pieces the player has placed on the quilt board, in order.
+    pieces:      Vec<Piece>,
+    /// The total currency the player has spent.
+    #[serde(default)]
+    spent:       usize,
 }
 
 impl PlayerState {
-    /// Creates a new player with the given amount of starting currency.
+    /// Creates a new player with the given amount of starting currency, capped at
+    /// [`MAX_CURRENCY`](constant.MAX_CURRENCY.html).
     pub fn new(dimension: Dimension, currency: usize) -> Self {
         PlayerState {
             quilt_board: QuiltBoard::new(dimension),
-            currency:    currency,
+            currency:    cmp::min(currency, MAX_CURRENCY),
             bonus:       0,
+            income:      0,
+            pieces:      Vec::new(),
+            spent:       0,
+        }
+    }
+
+    /// Gets a reference to the player’s quilt board.
+    pub fn quilt_board(&self) -> &QuiltBoard {
+        &self.quilt_board
+    }
+
+    /// Gets the player’s amount of currency.
+    pub fn currency(&self) -> usize {
+        self.currency
+    }
+
+    /// Gets the player’s bonus points.
+    pub fn bonus(&self) -> usize {
+        self.bonus
+    }
+
+    /// Views the pieces the player has bought and placed, in the order they were placed.
+    pub fn placed_pieces(&self) -> &[Piece] {
+        &self.pieces
+    }
+
+    /// Gets the amount the player collects each time they pass a collect square.
+    ///
+    /// This is the total `button_income` of the pieces they have placed.
+    pub fn income(&self) -> usize {
+        self.income
+    }
+
+    /// Adds the given amount to the player’s currency.
+    ///
+    /// Currency saturates at [`MAX_CURRENCY`](constant.MAX_CURRENCY.html) rather than
+    /// overflowing.
+    pub fn earn(&mut self, amount: usize) {
+        self.currency = cmp::min(self.currency.saturating_add(amount), MAX_CURRENCY);
+    }
+
+    /// Removes the given amount from the player’s currency.
+    ///
+    /// Returns `Err` without spending anything if the player cannot afford it.
+    pub fn spend(&mut self, amount: usize) -> QResult<()> {
+        if amount > self.currency {
+            return Err(PlayerError::InsufficientFunds);
         }
+
+        self.currency -= amount;
+        self.spent = self.spent.saturating_add(amount);
+        Ok(())
+    }
+
+    /// Gets the total currency the player has spent over the game.
+    pub fn total_spent(&self) -> usize {
+        self.spent
+    }
+
+    /// Adds the given number of bonus points, saturating rather than overflowing.
+    pub fn award_bonus(&mut self, points: usize) {
+        self.bonus = self.bonus.saturating_add(points);
+    }
+
+    /// Computes the player’s score: currency plus bonus, minus two for each uncovered position
+    /// of the quilt board.
+    ///
+    /// The arithmetic saturates, so huge currency or bonus values keep the score near
+    /// `i64::MAX` rather than wrapping negative.
+    pub fn score(&self) -> i64 {
+        let board = &self.quilt_board;
+        let uncovered = board.width() * board.height() - board.positions_covered();
+
+        saturating_i64(self.currency)
+            .saturating_add(saturating_i64(self.bonus))
+            .saturating_sub(saturating_i64(uncovered).saturating_mul(2))
+    }
+
+    /// Collects income once, as when passing a collect square on the time board.
+    pub fn collect(&mut self) {
+        let income = self.income();
+        self.earn(income);
+    }
+
+    /// Can the player afford the given piece and place it at the given position under the given
+    /// transformation?
+    ///
+    /// Returns `Err` of a reason if not.
+    pub fn can_buy_piece(&self, position: Position, piece: &Piece, transformation: Transformation)
+                         -> QResult<()>
+    {
+        if piece.cost() > self.currency {
+            return Err(PlayerError::InsufficientFunds);
+        }
+
+        self.quilt_board.can_add_piece(position, piece, transformation)
+    }
+
+    /// Pays for the given piece and adds it to the quilt board.
+    ///
+    /// Leaves the player unchanged if the piece cannot be bought or placed.
+    pub fn buy_piece(&mut self, position: Position, piece: Piece, transformation: Transformation)
+                     -> QResult<()>
+    {
+        self.can_buy_piece(position, &piece, transformation)?;
+        self.spend(piece.cost())?;
+        self.place_piece(position, piece, transformation)
+    }
+
+    /// Places the given piece on the player’s quilt board without paying for it.
+    ///
+    /// The piece still adds to the player’s income and placed pieces.
+    pub fn place_piece(&mut self, position: Position, piece: Piece, transformation: Transformation)
+                       -> QResult<()>
+    {
+        self.quilt_board.add_piece(position, &piece, transformation)?;
+        self.income += piece.button_income();
+        self.pieces.push(piece);
+        Ok(())
     }
 }
 
@@ -162,3 +330,126 @@ impl Default for PlayerState {
                          DEFAULT_STARTING_CURRENCY)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use piece::examples;
+
+    #[test]
+    fn play_order_from_players() {
+        let players = vec![Player::from_usize(2), Player::from_usize(0), Player::from_usize(1)];
+        let play_order = PlayOrder::from_players(players.clone());
+
+        assert_eq!(play_order.players().cloned().collect::<Vec<_>>(), players);
+        assert_eq!(play_order.current(), Some(&Player(2)));
+        assert_eq!(PlayOrder::from_players(vec![Player(0), Player(1)]),
+                   PlayOrder::new_in_order(2));
+    }
+
+    #[test]
+    fn play_order_position_of() {
+        let mut play_order = PlayOrder::new_in_order(3);
+
+        assert_eq!(play_order.position_of(Player(0)), Some(0));
+        assert_eq!(play_order.position_of(Player(1)), Some(1));
+        assert_eq!(play_order.position_of(Player(2)), Some(2));
+        assert_eq!(play_order.position_of(Player(3)), None);
+
+        play_order.rotate();
+        assert_eq!(play_order.position_of(Player(0)), Some(2));
+        assert_eq!(play_order.position_of(Player(1)), Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Players must be distinct")]
+    fn play_order_from_duplicate_players() {
+        PlayOrder::from_players(vec![Player(1), Player(1)]);
+    }
+
+    #[test]
+    fn rotate_play_order() {
+        let start = PlayOrder::new_in_order(3);
+        let mut play_order = start.clone();
+        assert_eq!(play_order.current(), Some(&Player(0)));
+
+        play_order.rotate();
+        assert_eq!(play_order.current(), Some(&Player(1)));
+        assert_eq!(play_order.players().cloned().collect::<Vec<_>>(),
+                   vec![Player(1), Player(2), Player(0)]);
+
+        play_order.rotate();
+        assert_eq!(play_order.current(), Some(&Player(2)));
+
+        play_order.rotate();
+        assert_eq!(play_order, start);
+        assert_eq!(play_order.pop(), Some(Player(0)));
+    }
+
+    #[test]
+    fn total_spent() {
+        let mut state = PlayerState::new(Dimension::square(9), 10);
+        assert_eq!(state.spend(3), Ok(()));
+        assert_eq!(state.spend(4), Ok(()));
+        assert_eq!(state.spend(4), Err(PlayerError::InsufficientFunds));
+
+        assert_eq!(state.total_spent(), 7);
+        assert_eq!(state.currency(), 3);
+    }
+
+    #[test]
+    fn earn_saturates() {
+        let mut state = PlayerState::new(Dimension::square(9), MAX_CURRENCY - 2);
+        state.earn(1);
+        assert_eq!(state.currency(), MAX_CURRENCY - 1);
+        state.earn(5);
+        assert_eq!(state.currency(), MAX_CURRENCY);
+
+        state.award_bonus(usize::MAX);
+        state.award_bonus(1);
+        assert_eq!(state.bonus(), usize::MAX);
+    }
+
+    #[test]
+    fn score_saturates() {
+        let mut state = PlayerState::new(Dimension::square(9), 0);
+        state.earn(usize::MAX);
+        state.award_bonus(usize::MAX);
+
+        // Currency plus bonus saturates before the 81 uncovered positions are subtracted.
+        assert_eq!(state.score(), i64::MAX - 162);
+
+        let mut other = PlayerState::new(Dimension::square(9), 100);
+        other.award_bonus(100);
+        assert!(state.score() > other.score());
+    }
+
+    #[test]
+    fn capped_currency_scores_highest() {
+        let mut rich = PlayerState::new(Dimension::square(9), 0);
+        rich.earn(MAX_CURRENCY - 1);
+        rich.earn(usize::MAX);
+        assert_eq!(rich.currency(), MAX_CURRENCY);
+
+        let poor = PlayerState::new(Dimension::square(9), 100);
+
+        assert!(rich.score() > 0);
+        assert!(rich.score() > poor.score());
+        assert_eq!(PlayerState::new(Dimension::square(9), usize::MAX).currency(), MAX_CURRENCY);
+    }
+
+    #[test]
+    fn income_from_placed_pieces() {
+        let mut state = PlayerState::new(Dimension::square(9), 10);
+        assert_eq!(state.income(), 0);
+
+        assert_eq!(state.buy_piece(Position::new(0, 0), examples::piece2(),
+                                   Transformation::identity()),
+                   Ok(()));
+        assert_eq!(state.income(), 3);
+        assert_eq!(state.currency(), 2);
+
+        state.collect();
+        assert_eq!(state.currency(), 5);
+    }
+}
@@ -1,11 +1,17 @@
 //! Positions, dimensions, and position transformations.
 
+use std::cmp;
 use std::default::Default;
 
+use piece::Piece;
+
 /// A position on the board or in a piece.
 ///
 /// Origin is in the upper left.
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+///
+/// Deserializes from either a map such as `{"x": 1, "y": 0}` or a compact pair such as
+/// `[1, 0]`, so piece positions may be written in either form.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 pub struct Position {
     pub x: usize,
     pub y: usize,
@@ -15,8 +21,8 @@ impl Position {
     /// Creates a new position with the given x and y coordinates.
     pub fn new(x: usize, y: usize) -> Self {
         Position {
-            x: x,
-            y: y,
+            x,
+            y,
         }
     }
 
@@ -29,6 +35,22 @@ impl Position {
             y: self.y + other.y,
         }
     }
+
+    /// Translates a position relative to another, returning `None` on overflow.
+    pub fn checked_translate(self, other: Position) -> Option<Self> {
+        Some(Position {
+            x: self.x.checked_add(other.x)?,
+            y: self.y.checked_add(other.y)?,
+        })
+    }
+
+    /// Translates a position relative to another, saturating each coordinate at `usize::MAX`.
+    pub fn saturating_translate(self, other: Position) -> Self {
+        Position {
+            x: self.x.saturating_add(other.x),
+            y: self.y.saturating_add(other.y),
+        }
+    }
 }
 
 /// The dimensions of a board or piece.
@@ -44,8 +66,17 @@ impl Dimension {
     /// Creates a new `Dimension` with the given width and height.
     pub fn new(width: usize, height: usize) -> Self {
         Dimension {
-            width: width,
-            height: height,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a new `Dimension` with the given width and height, unless either is zero.
+    pub fn try_new(width: usize, height: usize) -> Option<Self> {
+        if width == 0 || height == 0 {
+            None
+        } else {
+            Some(Self::new(width, height))
         }
     }
 
@@ -59,6 +90,26 @@ impl Dimension {
         p.x < self.width && p.y < self.height
     }
 
+    /// Do rectangles of this dimension and `other` overlap, when their upper left corners are
+    /// at `origin_a` and `origin_b`, respectively?
+    ///
+    /// Rectangles that merely share an edge do not overlap, and empty rectangles overlap nothing.
+    pub fn intersects_at(self, origin_a: Position, other: Dimension, origin_b: Position) -> bool {
+        self.width > 0 && self.height > 0 && other.width > 0 && other.height > 0 &&
+            origin_a.x < origin_b.x + other.width && origin_b.x < origin_a.x + self.width &&
+            origin_a.y < origin_b.y + other.height && origin_b.y < origin_a.y + self.height
+    }
+
+    /// Moves the given `Position` to the nearest position within this `Dimension`.
+    ///
+    /// Coordinates are clamped to 0 for an empty dimension.
+    pub fn clamp(self, p: Position) -> Position {
+        Position {
+            x: cmp::min(p.x, self.width.saturating_sub(1)),
+            y: cmp::min(p.y, self.height.saturating_sub(1)),
+        }
+    }
+
     /// Transposes (swaps) the width and height.
     pub fn transpose(self) -> Self {
         Dimension {
@@ -66,12 +117,51 @@ impl Dimension {
             height: self.width,
         }
     }
+
+    /// Gets an iterator over every position within this `Dimension`, in row-major order.
+    pub fn positions(self) -> DimensionPositions {
+        DimensionPositions {
+            width: self.width,
+            index: 0,
+            end:   self.width * self.height,
+        }
+    }
 }
 
+/// An iterator over the positions of a [`Dimension`](struct.Dimension.html) in row-major
+/// order.
+#[derive(Debug, Clone)]
+pub struct DimensionPositions {
+    width: usize,
+    index: usize,
+    end:   usize,
+}
+
+impl Iterator for DimensionPositions {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.end {
+            let result = Position::new(self.index % self.width, self.index / self.width);
+            self.index += 1;
+            Some(result)
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.index;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for DimensionPositions {}
+
 /// The rotation portion of a `Transformation`
 ///
 /// Allows rotating to any multiple of 90 degrees, including 0.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Rotation {
     /// The identity rotation.
     NoRotation,
@@ -101,6 +191,59 @@ impl Rotation {
         }
     }
 
+    /// The rotation 90 degrees further clockwise.
+    pub fn clockwise(self) -> Self {
+        use self::Rotation::*;
+
+        match self {
+            NoRotation   => Clockwise90,
+            Clockwise90  => Clockwise180,
+            Clockwise180 => Clockwise270,
+            Clockwise270 => NoRotation,
+        }
+    }
+
+    /// The rotation 90 degrees further counterclockwise.
+    pub fn counterclockwise(self) -> Self {
+        use self::Rotation::*;
+
+        match self {
+            NoRotation   => Clockwise270,
+            Clockwise90  => NoRotation,
+            Clockwise180 => Clockwise90,
+            Clockwise270 => Clockwise180,
+        }
+    }
+
+    /// Composes two rotations, applying this one first and then `other`.
+    pub fn compose(self, other: Rotation) -> Self {
+        Self::from_quarter_turns(self.to_quarter_turns() + other.to_quarter_turns())
+    }
+
+    /// The rotation by the given number of quarter turns clockwise (modulo 4).
+    pub fn from_quarter_turns(n: usize) -> Self {
+        use self::Rotation::*;
+
+        match n % 4 {
+            0 => NoRotation,
+            1 => Clockwise90,
+            2 => Clockwise180,
+            _ => Clockwise270,
+        }
+    }
+
+    /// The number of quarter turns clockwise of this rotation, from 0 to 3.
+    pub fn to_quarter_turns(self) -> usize {
+        use self::Rotation::*;
+
+        match self {
+            NoRotation   => 0,
+            Clockwise90  => 1,
+            Clockwise180 => 2,
+            Clockwise270 => 3,
+        }
+    }
+
     fn is_even(self) -> bool {
         self == Rotation::NoRotation || self == Rotation::Clockwise180
     }
@@ -109,7 +252,7 @@ impl Rotation {
 /// The flip portion of a `Transformation`.
 ///
 /// Can be a horizontal flip or no change.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Flip {
     /// The identity flip.
     Identity,
@@ -127,10 +270,19 @@ impl Flip {
             Horizontal  => Position::new(d.width - p.x - 1, p.y),
         }
     }
+
+    /// Composes two flips, applying this one first and then `other`.
+    ///
+    /// Two horizontal flips cancel out.
+    pub fn compose(self, other: Flip) -> Self {
+        use self::Flip::*;
+
+        if self == other { Identity } else { Horizontal }
+    }
 }
 
 /// Ways that a game piece can be positioned.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Transformation {
     rotation: Rotation,
     flip:     Flip
@@ -140,8 +292,8 @@ impl Transformation {
     /// Creates a new transformation as the composition of a rotation (first) and a flip (second).
     pub fn new(rotation: Rotation, flip: Flip) -> Self {
         Transformation {
-            rotation: rotation,
-            flip: flip,
+            rotation,
+            flip,
         }
     }
 
@@ -150,11 +302,47 @@ impl Transformation {
         Transformation::new(Rotation::NoRotation, Flip::Identity)
     }
 
+    /// The rotation portion, which is applied first.
+    pub fn rotation(self) -> Rotation {
+        self.rotation
+    }
+
+    /// The flip portion, which is applied after the rotation.
+    pub fn flip(self) -> Flip {
+        self.flip
+    }
+
+    /// All eight transformations, starting with the identity.
+    pub fn all() -> [Transformation; 8] {
+        use self::Rotation::*;
+        use self::Flip::*;
+
+        [
+            Transformation::new(NoRotation,   Identity),
+            Transformation::new(Clockwise90,  Identity),
+            Transformation::new(Clockwise180, Identity),
+            Transformation::new(Clockwise270, Identity),
+            Transformation::new(NoRotation,   Horizontal),
+            Transformation::new(Clockwise90,  Horizontal),
+            Transformation::new(Clockwise180, Horizontal),
+            Transformation::new(Clockwise270, Horizontal),
+        ]
+    }
+
     /// Applies this transformation to a dimension.
     pub fn apply_dim(self, d: Dimension) -> Dimension {
         self.rotation.apply_dim(d)
     }
 
+    /// Do this transformation and `other` produce the same shape when applied to the given piece?
+    pub fn equivalent_on(&self, other: &Transformation, piece: &Piece) -> bool {
+        let mut mine: Vec<Position> = piece.positions(*self).collect();
+        let mut theirs: Vec<Position> = piece.positions(*other).collect();
+        mine.sort();
+        theirs.sort();
+        mine == theirs
+    }
+
     /// Applies this tranformation to a position within the given dimension.
     pub fn apply(self, d: Dimension, p: Position) -> Position {
         let Transformation { rotation, flip } = self;
@@ -180,6 +368,46 @@ mod test {
         Position::new(x, y)
     }
 
+    #[test]
+    fn dimension_positions() {
+        assert_eq!(Dimension::new(3, 2).positions().collect::<Vec<_>>(),
+                   vec![pos(0, 0), pos(1, 0), pos(2, 0), pos(0, 1), pos(1, 1), pos(2, 1)]);
+        assert_eq!(Dimension::new(3, 2).positions().len(), 6);
+        assert_eq!(Dimension::new(0, 5).positions().next(), None);
+    }
+
+    #[test]
+    fn overflowing_translate() {
+        let big = pos(usize::MAX - 1, 3);
+
+        assert_eq!(big.checked_translate(pos(1, 1)), Some(pos(usize::MAX, 4)));
+        assert_eq!(big.checked_translate(pos(2, 1)), None);
+        assert_eq!(big.saturating_translate(pos(2, 1)), pos(usize::MAX, 4));
+        assert_eq!(pos(1, 2).saturating_translate(pos(3, 4)), pos(1, 2).translate(pos(3, 4)));
+    }
+
+    #[test]
+    fn rectangles_intersect() {
+        let d = Dimension::new(3, 2);
+
+        assert!(d.intersects_at(pos(0, 0), d, pos(2, 1)));
+        assert!(d.intersects_at(pos(2, 1), d, pos(0, 0)));
+        assert!(d.intersects_at(pos(0, 0), Dimension::square(1), pos(1, 1)));
+        assert!(! d.intersects_at(pos(0, 0), d, pos(3, 0)));
+        assert!(! d.intersects_at(pos(0, 0), d, pos(0, 2)));
+        assert!(! d.intersects_at(pos(0, 0), Dimension::new(0, 2), pos(1, 0)));
+    }
+
+    #[test]
+    fn clamp_position() {
+        let d = Dimension::square(9);
+
+        assert_eq!(d.clamp(pos(100, 37)), pos(8, 8));
+        assert_eq!(d.clamp(pos(3, 12)), pos(3, 8));
+        assert_eq!(d.clamp(pos(3, 4)), pos(3, 4));
+        assert_eq!(Dimension::new(0, 2).clamp(pos(3, 4)), pos(0, 1));
+    }
+
     #[test]
     fn transform_upper_left() {
         let d = Dimension::new(6, 4);
@@ -228,6 +456,21 @@ mod test {
         check(Clockwise270, Horizontal, 2, 3);
     }
 
+    #[test]
+    fn equivalent_transformations() {
+        use piece::examples;
+
+        let identity = Transformation::identity();
+        let half_turn = Transformation::new(Clockwise180, Identity);
+        let flipped = Transformation::new(NoRotation, Horizontal);
+
+        assert!(identity.equivalent_on(&half_turn, &examples::piece4()));
+        assert!(identity.equivalent_on(&flipped, &examples::piece4()));
+        assert!(identity.equivalent_on(&half_turn, &examples::piece1()));
+        assert!(! identity.equivalent_on(&half_turn, &examples::piece2()));
+        assert!(! identity.equivalent_on(&flipped, &examples::piece1()));
+    }
+
     #[test]
     fn transform_width_height() {
         let d = Dimension::new(2, 3);
@@ -240,4 +483,61 @@ mod test {
         assert_eq!(d1, d);
         assert_eq!(d2, Dimension::new(3, 2));
     }
+
+    #[test]
+    fn rotation_steps() {
+        assert_eq!(NoRotation.clockwise().clockwise().clockwise().clockwise(), NoRotation);
+        assert_eq!(NoRotation.counterclockwise(), Clockwise270);
+
+        for &r in &[NoRotation, Clockwise90, Clockwise180, Clockwise270] {
+            assert_eq!(r.clockwise().counterclockwise(), r);
+            assert_eq!(r.counterclockwise().clockwise(), r);
+        }
+    }
+
+    #[test]
+    fn rotation_compose() {
+        let d = Dimension::new(6, 4);
+        let p = Position::new(2, 1);
+        let rotations = [NoRotation, Clockwise90, Clockwise180, Clockwise270];
+
+        for &r1 in &rotations {
+            for &r2 in &rotations {
+                let sequenced = r2.apply(r1.apply_dim(d), r1.apply(d, p));
+                assert_eq!(r1.compose(r2).apply(d, p), sequenced);
+                assert_eq!(r1.compose(r2).apply_dim(d), r2.apply_dim(r1.apply_dim(d)));
+            }
+        }
+    }
+
+    #[test]
+    fn rotation_quarter_turns() {
+        for &r in &[NoRotation, Clockwise90, Clockwise180, Clockwise270] {
+            assert_eq!(Rotation::from_quarter_turns(r.to_quarter_turns()), r);
+        }
+
+        assert_eq!(Rotation::from_quarter_turns(5), Clockwise90);
+    }
+
+    #[test]
+    fn flip_compose() {
+        let d = Dimension::new(6, 4);
+        let p = Position::new(2, 1);
+        let flips = [Identity, Horizontal];
+
+        for &f1 in &flips {
+            for &f2 in &flips {
+                assert_eq!(f1.compose(f2).apply(d, p), f2.apply(d, f1.apply(d, p)));
+            }
+        }
+
+        assert_eq!(Horizontal.compose(Horizontal), Identity);
+    }
+
+    #[test]
+    fn dimension_try_new() {
+        assert_eq!(Dimension::try_new(0, 5), None);
+        assert_eq!(Dimension::try_new(5, 0), None);
+        assert_eq!(Dimension::try_new(3, 2), Some(Dimension::new(3, 2)));
+    }
 }
@@ -1,10 +1,11 @@
 //! The board on which the quilt is constructed.
 
+use std::{cmp, fmt};
 use std::default::Default;
 
 use result::{QResult, PlayerError};
 use piece::Piece;
-use position::{Position, Dimension, Transformation};
+use position::{Position, Dimension, Flip, Transformation};
 
 /// The board on which the quilt is constructed.
 ///
@@ -13,7 +14,7 @@ use position::{Position, Dimension, Transformation};
 ///  - rows.len() == height
 ///
 ///  - for row in rows { row.len() == width }
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct QuiltBoard {
     dimension: Dimension,
     rows:      Box<[Box<[bool]>]>,
@@ -24,6 +25,11 @@ pub const DEFAULT_DIMENSION: usize = 9;
 
 impl QuiltBoard {
     /// Creates a new, empty board of the given dimensions.
+    ///
+    /// A board with zero width or height has no positions, so no piece can be added to it and
+    /// it has no covered squares. Use
+    /// [`Dimension::try_new`](../position/struct.Dimension.html#method.try_new) to rule such
+    /// boards out.
     pub fn new(dimension: Dimension) -> Self {
         let mut rows = Vec::new();
 
@@ -32,11 +38,71 @@ impl QuiltBoard {
         }
 
         QuiltBoard {
-            dimension: dimension,
+            dimension,
             rows:      rows.into_boxed_slice(),
         }
     }
 
+    /// Changes the dimensions of the board, keeping the coverage of positions that remain in
+    /// bounds.
+    ///
+    /// Covered positions outside the new dimensions are discarded.
+    pub fn resize(&mut self, new_dimension: Dimension) {
+        let mut result = Self::new(new_dimension);
+
+        for (y, row) in self.rows.iter().enumerate().take(new_dimension.height) {
+            for (x, &b) in row.iter().enumerate().take(new_dimension.width) {
+                result.rows[y][x] = b;
+            }
+        }
+
+        *self = result;
+    }
+
+    /// Creates a board of the given dimensions from a bitmask as produced by
+    /// [`to_bitmask`](#method.to_bitmask).
+    ///
+    /// Returns `None` if the board would have more than 128 positions.
+    pub fn from_bitmask(dimension: Dimension, mask: u128) -> Option<Self> {
+        if dimension.width * dimension.height > 128 {
+            return None;
+        }
+
+        let mut result = Self::new(dimension);
+
+        for y in 0 .. dimension.height {
+            for x in 0 .. dimension.width {
+                result.rows[y][x] = mask & (1 << (y * dimension.width + x)) != 0;
+            }
+        }
+
+        Some(result)
+    }
+
+    /// Packs the covered positions into a bitmask, in row-major order starting from the least
+    /// significant bit.
+    ///
+    /// Returns `None` if the board has more than 128 positions.
+    pub fn to_bitmask(&self) -> Option<u128> {
+        let Dimension { width, height } = self.dimension;
+
+        if width * height > 128 {
+            return None;
+        }
+
+        let mut result = 0;
+
+        for y in 0 .. height {
+            for x in 0 .. width {
+                if self.rows[y][x] {
+                    result |= 1 << (y * width + x);
+                }
+            }
+        }
+
+        Some(result)
+    }
+
     /// Returns the number of squares covered by pieces.
     pub fn positions_covered(&self) -> usize {
         let mut result = 0;
@@ -50,6 +116,19 @@ impl QuiltBoard {
         result
     }
 
+    /// Returns the fraction of the board covered by pieces, from 0.0 to 1.0.
+    ///
+    /// A board with no positions is 0.0 covered.
+    pub fn fill_fraction(&self) -> f64 {
+        let area = self.width() * self.height();
+
+        if area == 0 {
+            0.0
+        } else {
+            self.positions_covered() as f64 / area as f64
+        }
+    }
+
     /// The dimensions of the board.
     pub fn dimension(&self) -> Dimension {
         self.dimension
@@ -76,17 +155,158 @@ impl QuiltBoard {
             self.rows[position.y][position.x]
     }
 
+    /// Views the coverage of row `y`, from left to right.
+    ///
+    /// Returns `None` if `y` is out of bounds.
+    pub fn row(&self, y: usize) -> Option<&[bool]> {
+        self.rows.get(y).map(|row| &**row)
+    }
+
+    /// Gets the coverage of column `x`, from top to bottom.
+    ///
+    /// Returns `None` if `x` is out of bounds.
+    pub fn column(&self, x: usize) -> Option<Vec<bool>> {
+        if x < self.width() {
+            Some(self.rows.iter().map(|row| row[x]).collect())
+        } else {
+            None
+        }
+    }
+
+    /// Gets the indices of the rows that are entirely covered, in increasing order.
+    pub fn completed_rows(&self) -> Vec<usize> {
+        (0 .. self.height())
+            .filter(|&y| self.row(y).is_some_and(|row| row.iter().all(|&b| b)))
+            .collect()
+    }
+
+    /// Gets the indices of the columns that are entirely covered, in increasing order.
+    pub fn completed_columns(&self) -> Vec<usize> {
+        (0 .. self.width())
+            .filter(|&x| self.column(x).is_some_and(|column| column.iter().all(|&b| b)))
+            .collect()
+    }
+
+    /// Is the coverage unchanged by a horizontal flip, which mirrors left and right?
+    pub fn is_horizontally_symmetric(&self) -> bool {
+        self.rows.iter().all(|row| row.iter().eq(row.iter().rev()))
+    }
+
+    /// Is the coverage unchanged by a vertical flip, which mirrors top and bottom?
+    pub fn is_vertically_symmetric(&self) -> bool {
+        self.rows.iter().eq(self.rows.iter().rev())
+    }
+
+    /// Finds the positions whose coverage differs between this board and `other`, in row-major
+    /// order.
+    ///
+    /// Returns `Err(PlayerError::DimensionMismatch)` if the boards have different dimensions.
+    pub fn diff(&self, other: &QuiltBoard) -> QResult<Vec<Position>> {
+        if self.dimension != other.dimension {
+            return Err(PlayerError::DimensionMismatch);
+        }
+
+        let mut result = Vec::new();
+
+        for (y, (mine, theirs)) in self.rows.iter().zip(other.rows.iter()).enumerate() {
+            for (x, (a, b)) in mine.iter().zip(theirs.iter()).enumerate() {
+                if a != b {
+                    result.push(Position::new(x, y));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Finds the smallest rectangle containing every covered position.
+    ///
+    /// Returns the rectangle’s upper left position and its dimension, or `None` if no positions
+    /// are covered.
+    pub fn covered_bounding_box(&self) -> Option<(Position, Dimension)> {
+        let mut result: Option<(Position, Position)> = None;
+
+        for (y, row) in self.rows.iter().enumerate() {
+            for (x, &b) in row.iter().enumerate() {
+                if ! b {
+                    continue;
+                }
+
+                result = Some(match result {
+                    None => (Position::new(x, y), Position::new(x, y)),
+                    Some((min, max)) => (Position::new(cmp::min(min.x, x), cmp::min(min.y, y)),
+                                         Position::new(cmp::max(max.x, x), cmp::max(max.y, y))),
+                });
+            }
+        }
+
+        result.map(|(min, max)| (min, Dimension::new(max.x - min.x + 1, max.y - min.y + 1)))
+    }
+
+    /// Counts the regions of orthogonally connected uncovered positions.
+    pub fn empty_region_count(&self) -> usize {
+        let Dimension { width, height } = self.dimension;
+        let mut visited = vec![vec![false; width]; height];
+        let mut stack = Vec::new();
+        let mut result = 0;
+
+        for y in 0 .. height {
+            for x in 0 .. width {
+                if visited[y][x] || self.rows[y][x] {
+                    continue;
+                }
+
+                result += 1;
+                visited[y][x] = true;
+                stack.push(Position::new(x, y));
+
+                while let Some(p) = stack.pop() {
+                    let mut neighbors = Vec::with_capacity(4);
+                    if p.x > 0 { neighbors.push(Position::new(p.x - 1, p.y)); }
+                    if p.y > 0 { neighbors.push(Position::new(p.x, p.y - 1)); }
+                    neighbors.push(Position::new(p.x + 1, p.y));
+                    neighbors.push(Position::new(p.x, p.y + 1));
+
+                    for q in neighbors {
+                        if self.is_position_in_bounds(q) && ! self.rows[q.y][q.x]
+                            && ! visited[q.y][q.x]
+                        {
+                            visited[q.y][q.x] = true;
+                            stack.push(q);
+                        }
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
     /// Is there a `size`-by-`size` square covered?
     pub fn is_square_covered(&self, size: usize) -> bool {
+        self.find_covered_square(size).is_some()
+    }
+
+    /// Finds the upper left corner of the first `size`-by-`size` covered square, in row-major
+    /// order.
+    ///
+    /// Returns `None` if `size` is zero or the square is wider or taller than the board, which
+    /// includes every square on a board with no positions.
+    pub fn find_covered_square(&self, size: usize) -> Option<Position> {
+        if size == 0 || size > self.dimension.width || size > self.dimension.height {
+            return None;
+        }
+
         for y in 0 .. self.dimension.height - size + 1 {
             for x in 0 .. self.dimension.width - size + 1 {
-                if self.is_square_covered_at(Position::new(x, y), size) {
-                    return true;
+                let position = Position::new(x, y);
+                if self.is_square_covered_at(position, size) {
+                    return Some(position);
                 }
             }
         }
 
-        false
+        None
     }
 
     /// Is there a `size`-by-`size` square covered with its upper left at the given position?
@@ -125,6 +345,139 @@ impl QuiltBoard {
         Ok(())
     }
 
+    /// Finds every position that prevents adding the given piece at the given position under the
+    /// given transformation, along with the reason.
+    ///
+    /// Returns an empty vector if the piece can be added.
+    pub fn placement_conflicts(&self, position: Position,
+                               piece: &Piece,
+                               transformation: Transformation)
+                               -> Vec<(Position, PlayerError)>
+    {
+        let mut result = Vec::new();
+
+        for p in piece.positions(transformation) {
+            let p = p.translate(position);
+
+            if p.x >= self.dimension.width {
+                result.push((p, PlayerError::PlacementOverhangsRight));
+            } else if p.y >= self.dimension.height {
+                result.push((p, PlayerError::PlacementOverhangsBottom));
+            } else if self.is_position_covered(p) {
+                result.push((p, PlayerError::PlacementOverlapsPiece));
+            }
+        }
+
+        result
+    }
+
+    /// Would placing the piece leave it orthogonally adjacent to an already covered position?
+    ///
+    /// Positions under the piece itself do not count, nor does the edge of the board.
+    pub fn placement_touches(&self, position: Position,
+                             piece: &Piece,
+                             transformation: Transformation)
+                             -> bool
+    {
+        let cells: Vec<Position> = piece.positions(transformation)
+            .map(|p| p.translate(position))
+            .collect();
+
+        cells.iter().any(|&p| {
+            neighbors(p).iter()
+                .filter_map(|&q| q)
+                .any(|q| ! cells.contains(&q) && self.is_position_covered(q))
+        })
+    }
+
+    /// Counts the empty positions that placing the piece would enclose.
+    ///
+    /// An empty position is enclosed when each of its orthogonal neighbors is covered or off
+    /// the edge of the board. Only positions that are enclosed after the placement but were not
+    /// before are counted, so this measures the holes a placement creates.
+    pub fn placement_gap_count(&self, position: Position,
+                               piece: &Piece,
+                               transformation: Transformation)
+                               -> usize
+    {
+        let cells: Vec<Position> = piece.positions(transformation)
+            .map(|p| p.translate(position))
+            .collect();
+
+        let is_enclosed = |p: Position, with_piece: bool| {
+            neighbors(p).iter().all(|&q| match q {
+                None    => true,
+                Some(q) => ! self.is_position_in_bounds(q) || self.is_position_covered(q) ||
+                    (with_piece && cells.contains(&q)),
+            })
+        };
+
+        let mut gaps: Vec<Position> = Vec::new();
+
+        for &cell in &cells {
+            for q in neighbors(cell).iter().filter_map(|&q| q) {
+                if self.is_position_in_bounds(q) && ! self.is_position_covered(q) &&
+                    ! cells.contains(&q) && ! gaps.contains(&q) &&
+                    is_enclosed(q, true) && ! is_enclosed(q, false)
+                {
+                    gaps.push(q);
+                }
+            }
+        }
+
+        gaps.len()
+    }
+
+    /// Gets every position and transformation at which the given piece can be added.
+    ///
+    /// Transformations that produce the same shape are only considered once.
+    pub fn legal_placements(&self, piece: &Piece) -> Vec<(Position, Transformation)> {
+        let mut result = Vec::new();
+
+        for transformation in piece.distinct_transformations() {
+            let dimension = piece.dimension(transformation);
+
+            if dimension.width > self.dimension.width || dimension.height > self.dimension.height {
+                continue;
+            }
+
+            for y in 0 .. self.dimension.height - dimension.height + 1 {
+                for x in 0 .. self.dimension.width - dimension.width + 1 {
+                    let position = Position::new(x, y);
+                    if self.can_add_piece(position, piece, transformation).is_ok() {
+                        result.push((position, transformation));
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Can the given piece be placed anywhere on the board, under any transformation?
+    ///
+    /// This stops at the first legal placement, so it is cheaper than checking whether
+    /// [`legal_placements`](#method.legal_placements) is empty.
+    pub fn can_fit_anywhere(&self, piece: &Piece) -> bool {
+        for &transformation in Transformation::all().iter() {
+            let dimension = piece.dimension(transformation);
+
+            if dimension.width > self.dimension.width || dimension.height > self.dimension.height {
+                continue;
+            }
+
+            for y in 0 .. self.dimension.height - dimension.height + 1 {
+                for x in 0 .. self.dimension.width - dimension.width + 1 {
+                    if self.can_add_piece(Position::new(x, y), piece, transformation).is_ok() {
+                        return true;
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
     /// Adds the given piece at the specified position under the given transformation.
     pub fn add_piece(&mut self, position: Position, piece: &Piece, transformation: Transformation)
                      -> QResult<()>
@@ -138,18 +491,136 @@ impl QuiltBoard {
         Ok(())
     }
 
-    #[cfg(test)]
-    fn visualize(&self) -> String {
-        let mut result = String::new();
+    /// Adds each of the given pieces in order, at the specified positions under the given
+    /// transformations.
+    ///
+    /// Either all the pieces are added or, if any placement fails, none are and the board is
+    /// unchanged.
+    pub fn add_pieces(&mut self, placements: &[(Position, &Piece, Transformation)])
+                      -> QResult<()>
+    {
+        let mut working = self.clone();
+
+        for &(position, piece, transformation) in placements {
+            working.add_piece(position, piece, transformation)?;
+        }
+
+        *self = working;
+        Ok(())
+    }
+
+    /// Creates a board from a drawing, as produced by its `Display` implementation.
+    ///
+    /// Each line is a row, with `#` for a covered position and any other character for an
+    /// uncovered position. Returns `Err(PlayerError::RaggedBoard)` if the rows differ in length.
+    pub fn from_ascii(art: &str) -> QResult<QuiltBoard> {
+        let rows: Vec<Box<[bool]>> = art.lines()
+            .map(|line| line.chars().map(|c| c == '#').collect::<Vec<_>>().into_boxed_slice())
+            .collect();
+
+        let width = rows.first().map_or(0, |row| row.len());
+
+        if rows.iter().any(|row| row.len() != width) {
+            return Err(PlayerError::RaggedBoard);
+        }
+
+        Ok(QuiltBoard {
+            dimension: Dimension::new(width, rows.len()),
+            rows:      rows.into_boxed_slice(),
+        })
+    }
+}
+
+/// The orthogonal neighbors of a position, or `None` for those that would have a negative
+/// coordinate.
+fn neighbors(p: Position) -> [Option<Position>; 4] {
+    [
+        p.x.checked_sub(1).map(|x| Position::new(x, p.y)),
+        p.y.checked_sub(1).map(|y| Position::new(p.x, y)),
+        Some(Position::new(p.x + 1, p.y)),
+        Some(Position::new(p.x, p.y + 1)),
+    ]
+}
+
+/// A candidate placement of a piece, as manipulated interactively before committing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlacementCursor {
+    piece:          Piece,
+    position:       Position,
+    transformation: Transformation,
+}
+
+impl PlacementCursor {
+    /// Creates a cursor for the given piece, untransformed in the upper left.
+    pub fn new(piece: Piece) -> Self {
+        PlacementCursor {
+            piece,
+            position:       Position::new(0, 0),
+            transformation: Transformation::identity(),
+        }
+    }
+
+    /// The piece being placed.
+    pub fn piece(&self) -> &Piece {
+        &self.piece
+    }
+
+    /// The position at which the piece would be placed.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// The transformation with which the piece would be placed.
+    pub fn transformation(&self) -> Transformation {
+        self.transformation
+    }
+
+    /// Moves the cursor to the given position.
+    pub fn move_to(&mut self, position: Position) {
+        self.position = position;
+    }
+
+    /// Rotates the piece a quarter turn clockwise, as currently displayed.
+    pub fn rotate(&mut self) {
+        let rotation = self.transformation.rotation();
+        let flip = self.transformation.flip();
+
+        // Flipping reverses the sense of any rotation applied before it.
+        let rotation = match flip {
+            Flip::Identity   => rotation.clockwise(),
+            Flip::Horizontal => rotation.counterclockwise(),
+        };
+
+        self.transformation = Transformation::new(rotation, flip);
+    }
+
+    /// Flips the piece horizontally, as currently displayed.
+    pub fn flip(&mut self) {
+        let rotation = self.transformation.rotation();
+        let flip = self.transformation.flip().compose(Flip::Horizontal);
+
+        self.transformation = Transformation::new(rotation, flip);
+    }
+
+    /// Can the piece be added to the given board where the cursor is?
+    ///
+    /// Returns `Err` of a reason if it cannot, as
+    /// [`QuiltBoard::can_add_piece`](struct.QuiltBoard.html#method.can_add_piece) does.
+    pub fn is_valid(&self, board: &QuiltBoard) -> QResult<()> {
+        board.can_add_piece(self.position, &self.piece, self.transformation)
+    }
+}
 
+impl fmt::Display for QuiltBoard {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         for row in &*self.rows {
             for &b in &**row {
-                result.push(if b {'#'} else {'-'});
+                formatter.write_str(if b {"#"} else {"-"})?;
             }
-            result.push('\n');
+            formatter.write_str("\n")?;
         }
 
-        result
+        Ok(())
     }
 }
 
@@ -191,7 +662,7 @@ mod test {
         let mut board = QuiltBoard::default();
 
         assert_eq!(board.add_piece(pos(2, 1), &examples::piece0(), Transformation::identity()), Ok(()));
-        assert_eq!(board.visualize(),
+        assert_eq!(board.to_string(),
                    "---------\n\
                     --##-----\n\
                     ---#-----\n\
@@ -204,7 +675,7 @@ mod test {
         assert_eq!(board.add_piece(pos(2, 2), &examples::piece0(),
                                    Transformation::new(Rotation::Clockwise180, Flip::Identity)),
                    Ok(()));
-        assert_eq!(board.visualize(),
+        assert_eq!(board.to_string(),
                    "---------\n\
                     --##-----\n\
                     --##-----\n\
@@ -221,7 +692,7 @@ mod test {
         assert_eq!(board.add_piece(pos(4, 1), &examples::piece0(),
                                    Transformation::new(Rotation::NoRotation, Flip::Horizontal)),
                    Ok(()));
-        assert_eq!(board.visualize(),
+        assert_eq!(board.to_string(),
         "---------\n\
                     --####---\n\
                     --###----\n\
@@ -238,7 +709,7 @@ mod test {
         assert_eq!(board.add_piece(pos(4, 2), &examples::piece0(),
                                    Transformation::new(Rotation::Clockwise180, Flip::Horizontal)),
                    Ok(()));
-        assert_eq!(board.visualize(),
+        assert_eq!(board.to_string(),
                    "---------\n\
                     --####---\n\
                     --####---\n\
@@ -275,4 +746,359 @@ mod test {
         assert_eq!(board.add_piece(pos(4, 1), &examples::piece0(), Transformation::identity()),
                    Ok(()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn placement_conflicts() {
+        let mut board = QuiltBoard::default();
+        let piece = examples::piece0();
+        assert_eq!(board.add_piece(pos(2, 1), &piece, Transformation::identity()), Ok(()));
+
+        assert_eq!(board.placement_conflicts(pos(3, 1), &piece, Transformation::identity()),
+                   vec![(pos(3, 1), PlayerError::PlacementOverlapsPiece)]);
+        assert_eq!(board.placement_conflicts(pos(8, 6), &piece, Transformation::identity()),
+                   vec![(pos(9, 6), PlayerError::PlacementOverhangsRight),
+                        (pos(9, 7), PlayerError::PlacementOverhangsRight),
+                        (pos(9, 8), PlayerError::PlacementOverhangsRight)]);
+        assert_eq!(board.placement_conflicts(pos(4, 1), &piece, Transformation::identity()),
+                   vec![]);
+    }
+
+    #[test]
+    fn legal_placements() {
+        let mut board = QuiltBoard::new(Dimension::new(3, 2));
+        let piece = examples::piece3();
+
+        // Each of the four rotations fits in two places.
+        assert_eq!(board.legal_placements(&piece).len(), 8);
+
+        assert_eq!(board.add_piece(pos(1, 0), &Piece::single_position(),
+                                   Transformation::identity()),
+                   Ok(()));
+        for (position, transformation) in board.legal_placements(&piece) {
+            assert_eq!(board.can_add_piece(position, &piece, transformation), Ok(()));
+        }
+        assert_eq!(board.legal_placements(&piece).len(), 2);
+        assert!(board.legal_placements(&examples::piece4()).is_empty());
+    }
+
+    #[test]
+    fn empty_regions() {
+        let mut board = QuiltBoard::new(Dimension::new(5, 4));
+        assert_eq!(board.empty_region_count(), 1);
+
+        for y in 0 .. 4 {
+            assert_eq!(board.add_piece(pos(2, y), &Piece::single_position(),
+                                       Transformation::identity()),
+                       Ok(()));
+        }
+        assert_eq!(board.empty_region_count(), 2);
+
+        let mut full = QuiltBoard::new(Dimension::new(2, 3));
+        assert_eq!(full.add_piece(pos(0, 0), &examples::piece0(), Transformation::identity()),
+                   Ok(()));
+        assert_eq!(full.empty_region_count(), 1);
+        assert_eq!(full.add_piece(pos(0, 1), &Piece::single_position(),
+                                  Transformation::identity()),
+                   Ok(()));
+        assert_eq!(full.add_piece(pos(0, 2), &Piece::single_position(),
+                                  Transformation::identity()),
+                   Ok(()));
+        assert_eq!(full.empty_region_count(), 0);
+    }
+
+    #[test]
+    fn bitmask_round_trip() {
+        let mut board = QuiltBoard::default();
+        assert_eq!(board.to_bitmask(), Some(0));
+
+        assert_eq!(board.add_piece(pos(0, 0), &examples::piece0(), Transformation::identity()),
+                   Ok(()));
+        assert_eq!(board.to_bitmask(), Some(0b10_000000010_000000011));
+
+        assert_eq!(board.add_piece(pos(5, 6), &examples::piece2(),
+                                   Transformation::new(Rotation::Clockwise90, Flip::Identity)),
+                   Ok(()));
+        let mask = board.to_bitmask().unwrap();
+        assert_eq!(QuiltBoard::from_bitmask(board.dimension(), mask), Some(board));
+
+        assert_eq!(QuiltBoard::new(Dimension::new(16, 9)).to_bitmask(), None);
+        assert_eq!(QuiltBoard::from_bitmask(Dimension::new(16, 9), 0), None);
+    }
+
+    #[test]
+    fn ascii_round_trip() {
+        let mut board = QuiltBoard::new(Dimension::new(5, 4));
+        assert_eq!(board.add_piece(pos(1, 0), &examples::piece3(), Transformation::identity()),
+                   Ok(()));
+
+        let art = board.to_string();
+        assert_eq!(art,
+                   "--#--\n\
+                    -##--\n\
+                    -----\n\
+                    -----\n");
+        assert_eq!(QuiltBoard::from_ascii(&art), Ok(board));
+
+        assert_eq!(QuiltBoard::from_ascii("##\n#\n"), Err(PlayerError::RaggedBoard));
+    }
+
+    #[test]
+    fn covered_bounding_box() {
+        let mut board = QuiltBoard::default();
+        assert_eq!(board.covered_bounding_box(), None);
+
+        assert_eq!(board.add_piece(pos(3, 2), &Piece::single_position(),
+                                   Transformation::identity()),
+                   Ok(()));
+        assert_eq!(board.covered_bounding_box(), Some((pos(3, 2), Dimension::square(1))));
+
+        let mut board = QuiltBoard::default();
+        let t = Transformation::new(Rotation::Clockwise90, Flip::Identity);
+        assert_eq!(board.add_piece(pos(4, 5), &examples::piece1(), t), Ok(()));
+        assert_eq!(board.covered_bounding_box(),
+                   Some((pos(4, 5), examples::piece1().dimension(t))));
+    }
+
+    #[test]
+    fn add_pieces_all_or_nothing() {
+        let mut board = QuiltBoard::default();
+        let piece = examples::piece0();
+        let t = Transformation::identity();
+
+        assert_eq!(board.add_pieces(&[(pos(0, 0), &piece, t),
+                                      (pos(2, 0), &piece, t),
+                                      (pos(1, 2), &piece, t)]),
+                   Err(PlayerError::PlacementOverlapsPiece));
+        assert_eq!(board, QuiltBoard::default());
+
+        assert_eq!(board.add_pieces(&[(pos(0, 0), &piece, t),
+                                      (pos(2, 0), &piece, t),
+                                      (pos(4, 0), &piece, t)]),
+                   Ok(()));
+        assert_eq!(board.positions_covered(), 12);
+    }
+
+    #[test]
+    fn diff_boards() {
+        let before = QuiltBoard::default();
+        let mut after = before.clone();
+        let piece = examples::piece0();
+        assert_eq!(after.add_piece(pos(2, 1), &piece, Transformation::identity()), Ok(()));
+
+        let mut expected: Vec<Position> = piece.positions(Transformation::identity())
+            .map(|p| p.translate(pos(2, 1)))
+            .collect();
+        expected.sort_by_key(|p| (p.y, p.x));
+
+        assert_eq!(before.diff(&after), Ok(expected.clone()));
+        assert_eq!(after.diff(&before), Ok(expected));
+        assert_eq!(after.diff(&after), Ok(vec![]));
+        assert_eq!(before.diff(&QuiltBoard::new(Dimension::new(9, 8))),
+                   Err(PlayerError::DimensionMismatch));
+    }
+
+    #[test]
+    fn resize_board() {
+        let mut board = QuiltBoard::new(Dimension::square(4));
+        assert_eq!(board.add_piece(pos(2, 1), &examples::piece0(), Transformation::identity()),
+                   Ok(()));
+
+        board.resize(Dimension::square(6));
+        assert_eq!(board.to_string(),
+                   "------\n\
+                    --##--\n\
+                    ---#--\n\
+                    ---#--\n\
+                    ------\n\
+                    ------\n");
+
+        board.resize(Dimension::new(3, 2));
+        assert_eq!(board.to_string(),
+                   "---\n\
+                    --#\n");
+    }
+
+    #[test]
+    fn placement_touches() {
+        let mut board = QuiltBoard::default();
+        let piece = examples::piece0();
+        let id = Transformation::identity();
+        assert_eq!(board.add_piece(pos(0, 0), &piece, id), Ok(()));
+
+        assert!(board.placement_touches(pos(2, 0), &piece, id));
+        assert!(board.placement_touches(pos(0, 3), &piece, id));
+        assert!(! board.placement_touches(pos(4, 4), &piece, id));
+        assert!(! board.placement_touches(pos(7, 6), &piece, id));
+    }
+
+    #[test]
+    fn fill_fraction() {
+        let board = QuiltBoard::from_ascii("####\n\
+                                            ##--\n\
+                                            --##\n\
+                                            ----\n").unwrap();
+        assert_eq!(board.fill_fraction(), 0.5);
+        assert_eq!(QuiltBoard::new(Dimension::new(0, 3)).fill_fraction(), 0.0);
+    }
+
+    #[test]
+    fn find_covered_square() {
+        let board = QuiltBoard::from_ascii("#----\n\
+                                            -###-\n\
+                                            -####\n\
+                                            -###-\n").unwrap();
+
+        assert_eq!(board.find_covered_square(1), Some(pos(0, 0)));
+        assert_eq!(board.find_covered_square(2), Some(pos(1, 1)));
+        assert_eq!(board.find_covered_square(3), Some(pos(1, 1)));
+        assert_eq!(board.find_covered_square(4), None);
+    }
+
+    #[test]
+    fn can_fit_anywhere() {
+        let empty = QuiltBoard::new(Dimension::square(4));
+        assert!(empty.can_fit_anywhere(&examples::piece0()));
+        assert!(! empty.can_fit_anywhere(&examples::piece4()));
+
+        let full = QuiltBoard::from_ascii("####\n####\n####\n####\n").unwrap();
+        assert!(! full.can_fit_anywhere(&Piece::single_position()));
+
+        let gap = QuiltBoard::from_ascii("##--\n###-\n####\n####\n").unwrap();
+        assert!(gap.can_fit_anywhere(&examples::piece3()));
+        assert!(! gap.can_fit_anywhere(&examples::piece0()));
+    }
+
+    #[test]
+    fn placement_gap_count() {
+        let board = QuiltBoard::from_ascii("-#--\n\
+                                            ----\n\
+                                            ----\n").unwrap();
+        let domino = Piece::new(vec![pos(0, 0), pos(1, 0)], 0, 0, 0);
+        let id = Transformation::identity();
+
+        // Covering (0, 1) and (1, 1) seals the corner at (0, 0).
+        assert_eq!(board.placement_gap_count(pos(0, 1), &domino, id), 1);
+        // Covering (2, 0) and (3, 0) leaves (2, 1) open below.
+        assert_eq!(board.placement_gap_count(pos(2, 0), &domino, id), 0);
+        assert_eq!(board.placement_gap_count(pos(1, 2), &domino, id), 0);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let mut board = QuiltBoard::new(Dimension::new(4, 3));
+        assert_eq!(board.add_piece(pos(1, 0), &examples::piece0(), Transformation::identity()),
+                   Ok(()));
+
+        assert_eq!(board.row(0), Some(&[false, true, true, false][..]));
+        assert_eq!(board.row(1), Some(&[false, false, true, false][..]));
+        assert_eq!(board.row(3), None);
+
+        assert_eq!(board.column(2), Some(vec![true, true, true]));
+        assert_eq!(board.column(0), Some(vec![false, false, false]));
+        assert_eq!(board.column(4), None);
+    }
+
+    #[test]
+    fn completed_rows_and_columns() {
+        let board = QuiltBoard::from_ascii("#---\n\
+                                            ####\n\
+                                            -#--\n").unwrap();
+        assert_eq!(board.completed_rows(), vec![1]);
+        assert!(board.completed_columns().is_empty());
+
+        let board = QuiltBoard::from_ascii("##-\n\
+                                            ##-\n").unwrap();
+        assert!(board.completed_rows().is_empty());
+        assert_eq!(board.completed_columns(), vec![0, 1]);
+    }
+
+    #[test]
+    fn symmetry() {
+        let board = QuiltBoard::from_ascii("-##-\n\
+                                            #--#\n\
+                                            -##-\n").unwrap();
+        assert!(board.is_horizontally_symmetric());
+        assert!(board.is_vertically_symmetric());
+
+        let board = QuiltBoard::from_ascii("##--\n\
+                                            #---\n\
+                                            ##--\n").unwrap();
+        assert!(! board.is_horizontally_symmetric());
+        assert!(board.is_vertically_symmetric());
+
+        let mut board = QuiltBoard::default();
+        assert_eq!(board.add_piece(pos(0, 0), &examples::piece0(), Transformation::identity()),
+                   Ok(()));
+        assert!(! board.is_horizontally_symmetric());
+        assert!(! board.is_vertically_symmetric());
+    }
+
+    #[test]
+    fn placement_cursor_rotation() {
+        let board = QuiltBoard::new(Dimension::new(3, 2));
+        let mut cursor = PlacementCursor::new(examples::piece0());
+
+        assert_eq!(cursor.is_valid(&board), Err(PlayerError::PlacementOverhangsBottom));
+
+        cursor.rotate();
+        assert_eq!(cursor.transformation(),
+                   Transformation::new(Rotation::Clockwise90, Flip::Identity));
+        assert_eq!(cursor.is_valid(&board), Ok(()));
+
+        cursor.move_to(pos(1, 0));
+        assert_eq!(cursor.is_valid(&board), Err(PlayerError::PlacementOverhangsRight));
+        cursor.move_to(pos(0, 0));
+
+        cursor.rotate();
+        assert_eq!(cursor.is_valid(&board), Err(PlayerError::PlacementOverhangsBottom));
+
+        cursor.rotate();
+        cursor.rotate();
+        assert_eq!(cursor.transformation(), Transformation::identity());
+    }
+
+    #[test]
+    fn placement_cursor_rotates_flipped_piece_clockwise() {
+        let mut cursor = PlacementCursor::new(examples::piece0());
+        cursor.flip();
+        let flipped = cursor.transformation();
+        cursor.rotate();
+
+        let dim = examples::piece0().dimension(flipped);
+        let mut expected: Vec<Position> = examples::piece0().positions(flipped)
+            .map(|p| Rotation::Clockwise90.apply(dim, p))
+            .collect();
+        let mut actual: Vec<Position> = examples::piece0().positions(cursor.transformation())
+            .collect();
+        expected.sort();
+        actual.sort();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn zero_dimension_board() {
+        let board = QuiltBoard::new(Dimension::new(0, 5));
+
+        assert!(! board.is_square_covered(1));
+        assert_eq!(board.find_covered_square(0), None);
+        assert_eq!(QuiltBoard::default().find_covered_square(0), None);
+        assert_eq!(board.positions_covered(), 0);
+        assert!(board.can_add_piece(pos(0, 0), &examples::piece3(), Transformation::identity())
+                .is_err());
+    }
+
+    #[test]
+    fn square_larger_than_board_is_not_covered() {
+        let mut board = QuiltBoard::default();
+        for y in 0 .. 9 {
+            for x in 0 .. 9 {
+                board.rows[y][x] = true;
+            }
+        }
+
+        assert!(board.is_square_covered(9));
+        assert!(! board.is_square_covered(10));
+        assert_eq!(QuiltBoard::new(Dimension::new(9, 3)).find_covered_square(4), None);
+    }
+}
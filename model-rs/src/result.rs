@@ -2,10 +2,15 @@
 
 use std::{error, fmt};
 
+use serde_json;
+
 /// The Quilting game result type.
 pub type QResult<T> = Result<T, PlayerError>;
 
-/// Errors that can be attributed to players.
+/// Errors from playing, configuring, or constructing a game.
+///
+/// Besides illegal moves by players, this covers invalid pieces and board drawings, game
+/// configurations that cannot be built, and mismatched boards.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PlayerError {
     /// The piece cannot be placed because it overhangs the right edge of the board.
@@ -18,24 +23,93 @@ pub enum PlayerError {
     TakeOverDepth,
     /// The piece queue does not have that many pieces.
     OutOfPieces,
+    /// The player cannot afford the piece.
+    InsufficientFunds,
+    /// No actions can be taken because the game is over.
+    GameOver,
+    /// It is not the given player’s turn.
+    NotPlayersTurn,
+    /// A piece must cover at least one position.
+    EmptyPiece,
+    /// A piece’s positions must be orthogonally connected.
+    DisconnectedPiece,
+    /// A board drawing does not have rows of equal length.
+    RaggedBoard,
+    /// The quilt board must have positive width and height.
+    EmptyQuilt,
+    /// The game needs at least two players.
+    TooFewPlayers,
+    /// The bonus square does not fit on the quilt board.
+    BonusSquareTooLarge,
+    /// The quilt boards have different dimensions.
+    DimensionMismatch,
+    /// A patch from the time board must be placed before any other action.
+    PendingPatchPlacement,
+    /// There is no patch waiting to be placed.
+    NoPendingPatch,
+    /// The piece weights add up to more than can be represented.
+    WeightOverflow,
 }
 
 impl fmt::Display for PlayerError {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        formatter.write_str(error::Error::description(self))
-    }
-}
-
-impl error::Error for PlayerError {
-    fn description(&self) -> &str {
         use self::PlayerError::*;
 
-        match *self {
+        formatter.write_str(match *self {
             PlacementOverhangsRight => "Piece placement overhangs right edge of quilt board",
             PlacementOverhangsBottom => "Piece placement overhangs bottom edge of quilt board",
             PlacementOverlapsPiece => "Piece placement overlaps another piece",
             TakeOverDepth => "Cannot take pieces from that deep in the queue",
             OutOfPieces => "The queue does not have that many pieces",
+            InsufficientFunds => "Player cannot afford that piece",
+            GameOver => "The game is over",
+            NotPlayersTurn => "It is not that player’s turn",
+            EmptyPiece => "Piece has no positions",
+            DisconnectedPiece => "Piece positions are not connected",
+            RaggedBoard => "Board rows have different lengths",
+            EmptyQuilt => "Quilt board has no positions",
+            TooFewPlayers => "Cannot play with fewer than two players",
+            BonusSquareTooLarge => "Bonus square does not fit on the quilt board",
+            DimensionMismatch => "Quilt boards have different dimensions",
+            PendingPatchPlacement => "A patch must be placed first",
+            NoPendingPatch => "There is no patch to place",
+            WeightOverflow => "Piece weights are too large in total",
+        })
+    }
+}
+
+impl error::Error for PlayerError {}
+
+/// Errors from loading a [`SavedGame`](../game_state/struct.SavedGame.html).
+#[derive(Debug)]
+pub enum LoadError {
+    /// The saved data is not valid JSON for a saved game.
+    Json(serde_json::Error),
+    /// The saved game has a format version this library does not know how to read.
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            LoadError::Json(ref e) => write!(formatter, "Invalid saved game: {}", e),
+            LoadError::UnsupportedVersion(version) =>
+                write!(formatter, "Unsupported saved game version: {}", version),
+        }
+    }
+}
+
+impl error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            LoadError::Json(ref e) => Some(e),
+            LoadError::UnsupportedVersion(_) => None,
         }
     }
 }
+
+impl From<serde_json::Error> for LoadError {
+    fn from(e: serde_json::Error) -> Self {
+        LoadError::Json(e)
+    }
+}
@@ -1,6 +1,6 @@
 //! The board along which players’ pieces move, tracking time.
 
-use std::cmp;
+use std::{cmp, fmt, iter, slice};
 use std::default::Default;
 
 use serde_json;
@@ -8,7 +8,12 @@ use serde_json;
 use piece::Piece;
 use player::{Player, PlayOrder, Players};
 
-const TIME_BOARD_JSON: &'static [u8] = include_bytes!("../data/time_board.json");
+const TIME_BOARD_JSON: &[u8] = include_bytes!("../data/time_board.json");
+
+/// Deserializes the squares of the default time board.
+pub fn default_squares() -> Vec<Square> {
+    serde_json::from_slice(TIME_BOARD_JSON).expect("default time board is valid")
+}
 
 /// A single square on the time board.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -20,20 +25,79 @@ pub struct Square {
     /// Whether to collect money when landing on or passing this square.
     #[serde(default)]
     collect: bool,
+    /// A fixed amount of money paid when landing on or passing this square, regardless of the
+    /// player’s pieces.
+    ///
+    /// When present, this replaces collecting: the square pays only the fixed amount, even if
+    /// `collect` is also set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    income:  Option<usize>,
     /// The players currently sitting on this square, in their order of play.
     #[serde(default = "PlayOrder::empty")]
     players: PlayOrder,
 }
 
+/// What happens when a player lands on or passes a [`Square`](struct.Square.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SquareKind {
+    /// Nothing happens.
+    Empty,
+    /// The player collects money.
+    Collect,
+    /// The player takes a piece.
+    Patch,
+    /// The player takes a piece and collects money.
+    PatchAndCollect,
+    /// The player is paid a fixed amount.
+    Income,
+    /// The player takes a piece and is paid a fixed amount.
+    PatchAndIncome,
+}
+
+impl Default for Square {
+    fn default() -> Self {
+        Square {
+            piece:   None,
+            collect: false,
+            income:  None,
+            players: PlayOrder::empty(),
+        }
+    }
+}
+
 impl Square {
     /// Views the `Piece` to be taken when landing on or passing this square, if any.
     pub fn piece(&self) -> Option<&Piece> {
         self.piece.as_ref()
     }
 
+    /// Gets whether a `Piece` remains to be taken from this square.
+    pub fn has_piece(&self) -> bool {
+        self.piece.is_some()
+    }
+
     /// Gets whether to collect money when landing on or passing this square.
+    ///
+    /// This is `false` if the square pays a fixed [`income`](#method.income) instead.
     pub fn collect(&self) -> bool {
-        self.collect
+        self.collect && self.income.is_none()
+    }
+
+    /// Gets the fixed amount of money paid when landing on or passing this square, if any.
+    pub fn income(&self) -> Option<usize> {
+        self.income
+    }
+
+    /// Classifies this square by what happens when a player lands on or passes it.
+    pub fn kind(&self) -> SquareKind {
+        match (self.has_piece(), self.income.is_some(), self.collect) {
+            (false, true,  _)     => SquareKind::Income,
+            (true,  true,  _)     => SquareKind::PatchAndIncome,
+            (false, false, false) => SquareKind::Empty,
+            (false, false, true)  => SquareKind::Collect,
+            (true,  false, false) => SquareKind::Patch,
+            (true,  false, true)  => SquareKind::PatchAndCollect,
+        }
     }
 
     /// Gets whether this square has a player on it.
@@ -42,20 +106,38 @@ impl Square {
     }
 
     /// Gets the sequence of players waiting on this square.
-    pub fn players(&self) -> Players {
+    pub fn players(&self) -> Players<'_> {
         self.players.players()
     }
 }
 
 /// The result of moving along the `TimeBoard`.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MoveResult {
     /// Any pieces acquired from the move.
-    pub pieces:   Vec<Piece>,
+    pub pieces:       Vec<Piece>,
     /// The number of collections resulting from the move.
-    pub collects: usize,
+    pub collects:     usize,
+    /// The total fixed income paid by squares passed during the move.
+    pub fixed_income: usize,
     /// The actual distance moved.
-    pub distance: usize,
+    pub distance:     usize,
+    /// The distance requested, which exceeds `distance` if the move was cut short by the end of
+    /// the board.
+    pub requested:    usize,
+}
+
+/// Something that happens to a player on a square they land on or pass during a move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SquareEvent {
+    /// Nothing happens on the square.
+    Pass,
+    /// The player collects their income.
+    Collect,
+    /// The player is paid the given fixed income.
+    Income(usize),
+    /// The player picks up the square’s patch.
+    Patch,
 }
 
 /// Builder for configuring and constructing `TimeBoard`s.
@@ -67,7 +149,14 @@ pub struct TimeBoardBuilder {
 impl TimeBoardBuilder {
     /// Configures the time board to use the default time board.
     pub fn new() -> Self {
-        Self::from_slice(TIME_BOARD_JSON).unwrap()
+        Self::from_squares(default_squares())
+    }
+
+    /// Configures the time board to have the given squares.
+    pub fn from_squares(squares: Vec<Square>) -> Self {
+        TimeBoardBuilder {
+            squares: squares.into_boxed_slice(),
+        }
     }
 
     /// Deserializes the time board configuration from JSON.
@@ -77,6 +166,33 @@ impl TimeBoardBuilder {
         })
     }
 
+    /// Changes the number of squares, truncating the board or padding it with empty squares.
+    ///
+    /// # Errors
+    ///
+    /// Panics if `length < 2`.
+    pub fn length(self, length: usize) -> Self {
+        assert!(length >= 2, "Time board needs at least two squares");
+
+        let mut squares = self.squares.into_vec();
+        squares.resize(length, Square::default());
+
+        TimeBoardBuilder {
+            squares: squares.into_boxed_slice(),
+        }
+    }
+
+    /// Places a 1x1 patch on the given square, to be taken by the first player to pass it.
+    ///
+    /// # Errors
+    ///
+    /// Panics if `square_index` is not a square of the board.
+    pub fn add_patch(mut self, square_index: usize) -> Self {
+        assert!(square_index < self.squares.len(), "Patch square out of range");
+        self.squares[square_index].piece = Some(Piece::single_position());
+        self
+    }
+
     pub fn build(mut self, play_order: PlayOrder) -> TimeBoard {
         assert!(play_order.len() >= 2, "Cannot play with fewer than two players");
 
@@ -111,7 +227,12 @@ pub struct TimeBoard {
 impl TimeBoard {
     /// Views the squares of the time board.
     pub fn squares(&self) -> &[Square] {
-        &*self.squares
+        &self.squares
+    }
+
+    /// Gets an iterator over the squares of the time board with their indices.
+    pub fn iter(&self) -> iter::Enumerate<slice::Iter<'_, Square>> {
+        self.squares.iter().enumerate()
     }
 
     /// Gets the index of the last square.
@@ -146,6 +267,34 @@ impl TimeBoard {
         }
     }
 
+    /// Returns the board position of the given player, if they are on the board.
+    pub fn index_of(&self, player: &Player) -> Option<usize> {
+        self.squares().iter().position(|square| square.players().any(|p| p == player))
+    }
+
+    /// Returns the number of squares the given player has left to move before reaching the end.
+    ///
+    /// Returns `None` if the player is not on the board.
+    pub fn distance_to_end(&self, player: Player) -> Option<usize> {
+        self.index_of(&player).map(|i| self.index_of_last_square() - i)
+    }
+
+    /// Has the given player reached the last square?
+    ///
+    /// Returns `false` if the player is not on the board.
+    pub fn has_finished(&self, player: Player) -> bool {
+        self.index_of(&player) == Some(self.index_of_last_square())
+    }
+
+    /// Has every player reached the last square?
+    ///
+    /// Since the current player is the one furthest behind, this agrees with
+    /// [`is_game_over`](#method.is_game_over), but it checks every square rather than relying on
+    /// that invariant.
+    pub fn all_finished(&self) -> bool {
+        self.squares[.. self.index_of_last_square()].iter().all(|square| ! square.has_player())
+    }
+
     /// Returns the board position of the player whose turn will be next.
     ///
     /// This is the position that the current player must pass to complete their turn.
@@ -165,7 +314,23 @@ impl TimeBoard {
         unreachable!("There must be at least two players");
     }
 
+    /// Moves the current player forward by the given distance, stopping at the last square.
+    ///
+    /// # Errors
+    ///
+    /// Panics if `distance` is 0 or the game is over.
     pub fn move_player(&mut self, distance: usize) -> MoveResult {
+        self.move_player_detailed(distance).0
+    }
+
+    /// Like [`move_player`](#method.move_player), but also lists what happened on each square
+    /// passed, in the order passed.
+    ///
+    /// Each square passed (or landed on) appears at least once with its index. A square where
+    /// several things happen appears once for each, in the order patch, collect, income.
+    pub fn move_player_detailed(&mut self, distance: usize)
+                                -> (MoveResult, Vec<(usize, SquareEvent)>)
+    {
         assert!(distance > 0, "Cannot move distance of 0");
         assert!(! self.is_game_over(), "Cannot move if game is over");
 
@@ -178,22 +343,39 @@ impl TimeBoard {
         self.squares[stop].players.push(player);
 
         let mut result = MoveResult {
-            pieces:   Vec::new(),
-            collects: 0,
-            distance: stop - start,
+            pieces:       Vec::new(),
+            collects:     0,
+            fixed_income: 0,
+            distance:     stop - start,
+            requested:    distance,
         };
 
-        for square in &mut self.squares[start + 1 .. stop + 1] {
+        let mut events = Vec::new();
+
+        for (i, square) in self.squares.iter_mut().enumerate().take(stop + 1).skip(start + 1) {
+            let before = events.len();
+
             if let Some(piece) = square.piece.take() {
-                result.pieces.push(piece)
+                result.pieces.push(piece);
+                events.push((i, SquareEvent::Patch));
             }
 
             if square.collect() {
                 result.collects += 1;
+                events.push((i, SquareEvent::Collect));
+            }
+
+            if let Some(income) = square.income() {
+                result.fixed_income += income;
+                events.push((i, SquareEvent::Income(income)));
+            }
+
+            if events.len() == before {
+                events.push((i, SquareEvent::Pass));
             }
         }
 
-        result
+        (result, events)
     }
 }
 
@@ -203,11 +385,44 @@ impl Default for TimeBoard {
     }
 }
 
+/// Draws the track on one line, one bracketed square at a time.
+///
+/// Each square shows `C` if it collects, `P` if it still has a patch, `+n` if it pays a fixed
+/// income of `n`, and then the numbers of the players on it in their order of play. For
+/// example, `[01][][C][P][+2]`.
+impl fmt::Display for TimeBoard {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for square in self.squares() {
+            formatter.write_str("[")?;
+
+            if square.collect() {
+                formatter.write_str("C")?;
+            }
+
+            if square.has_piece() {
+                formatter.write_str("P")?;
+            }
+
+            if let Some(income) = square.income() {
+                write!(formatter, "+{}", income)?;
+            }
+
+            for player in square.players() {
+                write!(formatter, "{}", player.to_usize())?;
+            }
+
+            formatter.write_str("]")?;
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
-    static TEST_BOARD: &'static [u8] =
+    static TEST_BOARD: &[u8] =
         br#"[
           {},
           {},
@@ -256,7 +471,7 @@ mod test {
         let mut time_board = TimeBoardBuilder::from_slice(TEST_BOARD).unwrap()
             .build(play_order.clone());
 
-        assert_eq!(time_board.current_player(), play_order.players().nth(0));
+        assert_eq!(time_board.current_player(), play_order.players().next());
         assert_eq!(time_board.index_of_current_player(), 0);
         assert_eq!(time_board.index_of_next_player(), 0);
 
@@ -286,7 +501,7 @@ mod test {
         assert_eq!(mr.collects, 1);
         assert_eq!(mr.distance, 3);
 
-        assert_eq!(time_board.current_player(), play_order.players().nth(0));
+        assert_eq!(time_board.current_player(), play_order.players().next());
         assert_eq!(time_board.index_of_current_player(), 2);
         assert_eq!(time_board.index_of_next_player(), 5);
 
@@ -306,7 +521,7 @@ mod test {
         assert_eq!(mr.collects, 2);
         assert_eq!(mr.distance, 5);
 
-        assert_eq!(time_board.current_player(), play_order.players().nth(0));
+        assert_eq!(time_board.current_player(), play_order.players().next());
         assert_eq!(time_board.index_of_current_player(), 9);
         assert_eq!(time_board.index_of_next_player(), 10);
 
@@ -330,4 +545,218 @@ mod test {
         assert_eq!(time_board.current_player(), None);
         assert!(time_board.is_game_over());
     }
+
+    #[test]
+    fn iterate_squares() {
+        let play_order = PlayOrder::new(2);
+        let time_board = TimeBoardBuilder::from_slice(TEST_BOARD).unwrap()
+            .build(play_order.clone());
+
+        assert_eq!(time_board.iter().count(), time_board.squares().len());
+
+        let (index, square) = time_board.iter().next().unwrap();
+        assert_eq!(index, 0);
+        assert!(square.players().eq(play_order.players()));
+
+        let (index, square) = time_board.iter().find(|&(_, square)| square.has_piece()).unwrap();
+        assert_eq!(index, 10);
+        assert_eq!(square.piece(), Some(&Piece::single_position()));
+        assert!(! square.has_player());
+    }
+
+    #[test]
+    fn square_kinds() {
+        let time_board = TimeBoardBuilder::from_slice(TEST_BOARD).unwrap()
+            .add_patch(5)
+            .build(PlayOrder::new(2));
+        let squares = time_board.squares();
+
+        assert_eq!(squares[0].kind(), SquareKind::Empty);
+        assert_eq!(squares[5].kind(), SquareKind::PatchAndCollect);
+        assert_eq!(squares[7].kind(), SquareKind::Collect);
+        assert_eq!(squares[10].kind(), SquareKind::Patch);
+    }
+
+    #[test]
+    fn income_square_kinds() {
+        let time_board = TimeBoardBuilder::from_slice(br#"[{}, {"income": 2},
+                                                            {"income": 2, "collect": true}]"#)
+            .unwrap()
+            .add_patch(2)
+            .build(PlayOrder::new(2));
+        let squares = time_board.squares();
+
+        assert_eq!(squares[1].kind(), SquareKind::Income);
+        assert_eq!(squares[2].kind(), SquareKind::PatchAndIncome);
+    }
+
+    #[test]
+    fn income_replaces_collect() {
+        let mut time_board = TimeBoardBuilder::from_slice(br#"[{}, {"income": 2, "collect": true},
+                                                                {}]"#)
+            .unwrap()
+            .build(PlayOrder::new_in_order(2));
+
+        assert!(! time_board.squares()[1].collect());
+
+        let (move_result, events) = time_board.move_player_detailed(1);
+        assert_eq!(move_result.collects, 0);
+        assert_eq!(move_result.fixed_income, 2);
+        assert_eq!(events, vec![(1, SquareEvent::Income(2))]);
+    }
+
+    #[test]
+    fn distance_to_end() {
+        let play_order = PlayOrder::new_in_order(2);
+        let first = play_order.players().next().unwrap().clone();
+        let second = play_order.players().nth(1).unwrap().clone();
+
+        let mut time_board = TimeBoardBuilder::from_slice(TEST_BOARD).unwrap()
+            .build(play_order);
+        time_board.move_player(2);
+
+        assert_eq!(time_board.index_of(&first), Some(2));
+        assert_eq!(time_board.distance_to_end(first), Some(12));
+        assert_eq!(time_board.distance_to_end(second), Some(14));
+
+        let absent = PlayOrder::new_in_order(3).players().nth(2).unwrap().clone();
+        assert_eq!(time_board.distance_to_end(absent), None);
+    }
+
+    #[test]
+    fn move_past_end_reports_requested() {
+        let mut time_board = TimeBoardBuilder::from_slice(TEST_BOARD).unwrap()
+            .build(PlayOrder::new(2));
+
+        let mr = time_board.move_player(3);
+        assert_eq!(mr.distance, 3);
+        assert_eq!(mr.requested, 3);
+
+        let mr = time_board.move_player(20);
+        assert_eq!(mr.distance, 14);
+        assert_eq!(mr.requested, 20);
+    }
+
+    #[test]
+    fn board_length() {
+        let time_board = TimeBoardBuilder::new().length(20).build(PlayOrder::new(2));
+        assert_eq!(time_board.index_of_last_square(), 19);
+        assert!(! time_board.is_game_over());
+
+        let time_board = TimeBoardBuilder::from_slice(TEST_BOARD).unwrap()
+            .length(20)
+            .build(PlayOrder::new(2));
+        assert!(time_board.squares()[14].collect());
+        assert_eq!(time_board.squares()[19], Square::default());
+    }
+
+    #[test]
+    fn fixed_income_squares() {
+        let json = br#"[{}, {"income": 2}, {"collect": true}, {"income": 3}, {}]"#;
+        let mut time_board = TimeBoardBuilder::from_slice(json).unwrap()
+            .build(PlayOrder::new(2));
+
+        let mr = time_board.move_player(2);
+        assert_eq!(mr.fixed_income, 2);
+        assert_eq!(mr.collects, 1);
+
+        let mr = time_board.move_player(4);
+        assert_eq!(mr.fixed_income, 5);
+        assert_eq!(mr.collects, 1);
+    }
+
+    #[test]
+    fn collect_multiple_patches() {
+        let mut time_board = TimeBoardBuilder::from_slice(TEST_BOARD).unwrap()
+            .add_patch(2)
+            .add_patch(4)
+            .build(PlayOrder::new(2));
+
+        let mr = time_board.move_player(5);
+        assert_eq!(mr.pieces, vec![Piece::single_position(), Piece::single_position()]);
+        assert_eq!(mr.collects, 1);
+
+        // The other player passes the same squares but finds the patches gone.
+        let mr = time_board.move_player(6);
+        assert_eq!(mr.pieces, vec![]);
+        assert_eq!(mr.collects, 1);
+    }
+
+    #[test]
+    fn move_result_round_trip() {
+        let move_result = MoveResult {
+            pieces:       vec![Piece::single_position()],
+            collects:     1,
+            fixed_income: 0,
+            distance:     3,
+            requested:    4,
+        };
+
+        let json = serde_json::to_string(&move_result).unwrap();
+        assert_eq!(serde_json::from_str::<MoveResult>(&json).unwrap(), move_result);
+    }
+
+    #[test]
+    fn has_finished() {
+        let mut time_board = TimeBoardBuilder::from_slice(TEST_BOARD).unwrap()
+            .build(PlayOrder::new_in_order(2));
+        let first = time_board.current_player().unwrap().clone();
+
+        time_board.move_player(20);
+
+        assert!(time_board.has_finished(first));
+        assert!(! time_board.has_finished(Player::from_usize(1)));
+        assert!(! time_board.has_finished(Player::from_usize(5)));
+    }
+
+    #[test]
+    fn move_player_detailed() {
+        let mut time_board = TimeBoardBuilder::from_slice(TEST_BOARD).unwrap()
+            .build(PlayOrder::new_in_order(2));
+
+        // [01][][][][][C][][C][][C][P][][][][C] → [1][][][][][C][][C][][C][0][][][][C]
+        let (move_result, events) = time_board.move_player_detailed(10);
+
+        assert_eq!(move_result.collects, 3);
+        assert_eq!(move_result.pieces, vec![Piece::single_position()]);
+        assert_eq!(events, vec![(1, SquareEvent::Pass),
+                                (2, SquareEvent::Pass),
+                                (3, SquareEvent::Pass),
+                                (4, SquareEvent::Pass),
+                                (5, SquareEvent::Collect),
+                                (6, SquareEvent::Pass),
+                                (7, SquareEvent::Collect),
+                                (8, SquareEvent::Pass),
+                                (9, SquareEvent::Collect),
+                                (10, SquareEvent::Patch)]);
+    }
+
+    #[test]
+    fn default_squares_match_builder() {
+        let squares = default_squares();
+        assert_eq!(TimeBoardBuilder::from_squares(squares.clone()), TimeBoardBuilder::new());
+        assert!(squares.iter().any(Square::has_piece));
+    }
+
+    #[test]
+    fn display_track() {
+        let mut time_board = TimeBoardBuilder::from_slice(TEST_BOARD).unwrap()
+            .build(PlayOrder::new_in_order(2));
+
+        assert_eq!(time_board.to_string(), "[01][][][][][C][][C][][C][P][][][][C]");
+
+        time_board.move_player(2);
+        assert_eq!(time_board.to_string(), "[1][][0][][][C][][C][][C][P][][][][C]");
+
+        time_board.move_player(10);
+        assert_eq!(time_board.to_string(), "[][][0][][][C][][C][][C][1][][][][C]");
+    }
+
+    #[test]
+    fn display_income_square() {
+        let time_board = TimeBoardBuilder::from_slice(br#"[{}, {"income": 2}]"#).unwrap()
+            .build(PlayOrder::new_in_order(2));
+
+        assert_eq!(time_board.to_string(), "[01][+2]");
+    }
 }
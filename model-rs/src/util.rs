@@ -0,0 +1,60 @@
+//! Small helpers shared by the rest of the model.
+
+use std::convert::TryFrom;
+
+use rand;
+
+/// Converts a count to an `i64`, saturating at `i64::MAX` rather than wrapping negative.
+pub fn saturating_i64(n: usize) -> i64 {
+    i64::try_from(n).unwrap_or(i64::MAX)
+}
+
+/// Shuffles a slice in place using the
+/// [Fisher-Yates shuffle](https://en.wikipedia.org/wiki/Fisher%E2%80%93Yates_shuffle).
+///
+/// Every permutation is equally likely, given a uniform `rng`.
+pub fn shuffle<R: rand::Rng, T>(rng: &mut R, slice: &mut [T]) {
+    use rand::distributions::{IndependentSample, Range};
+
+    for i in (1 .. slice.len()).rev() {
+        let range = Range::new(0, i + 1);
+        let j = range.ind_sample(rng);
+        slice.swap(i, j);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{SeedableRng, StdRng};
+
+    #[test]
+    fn shuffle_is_permutation() {
+        let mut rng = StdRng::from_seed(&[1, 2, 3][..]);
+        let original: Vec<usize> = (0 .. 20).collect();
+
+        for _ in 0 .. 10 {
+            let mut shuffled = original.clone();
+            shuffle(&mut rng, &mut shuffled);
+            shuffled.sort();
+            assert_eq!(shuffled, original);
+        }
+    }
+
+    #[test]
+    fn shuffle_can_leave_element_in_place() {
+        let mut rng = StdRng::from_seed(&[1, 2, 3][..]);
+        let fixed = (0 .. 100).any(|_| {
+            let mut v = [0, 1];
+            shuffle(&mut rng, &mut v);
+            v == [0, 1]
+        });
+        assert!(fixed);
+    }
+
+    #[test]
+    fn saturating_i64_does_not_wrap() {
+        assert_eq!(saturating_i64(7), 7);
+        assert_eq!(saturating_i64(usize::MAX), i64::MAX);
+    }
+}